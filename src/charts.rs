@@ -0,0 +1,371 @@
+//! Burndown and velocity figures computed from story points and the status
+//! history of stories, shared by the terminal charts and the SVG export.
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+
+use crate::error::{Error, Result};
+use crate::models::{DBState, SprintState, Story};
+
+/// Number of completed sprints shown by the velocity chart.
+pub const VELOCITY_SPRINTS: usize = 6;
+
+/// Remaining story points per day.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Burndown {
+    pub title: String,
+    pub days: Vec<NaiveDate>,
+    /// Points still open at the end of each day, `None` for days that have
+    /// not happened yet.
+    pub remaining: Vec<Option<u64>>,
+    /// Points in scope today.
+    pub total: u64,
+    /// Whether the range has a planned end, so an ideal line from `total`
+    /// down to zero can be drawn.
+    pub ideal: bool,
+}
+
+impl Burndown {
+    /// Points on the ideal line at `day`, for burndowns that have one.
+    pub fn ideal_at(&self, day: usize) -> Option<f64> {
+        if !self.ideal {
+            return None;
+        }
+        let last = self
+            .days
+            .len()
+            .saturating_sub(1)
+            .max(1);
+        Some(self.total as f64 * (1.0 - day as f64 / last as f64))
+    }
+}
+
+/// Points one completed sprint took on and finished.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VelocityBar {
+    pub sprint_id: u32,
+    pub name: String,
+    pub committed: u64,
+    pub completed: u64,
+}
+
+// summed as u64 so boards with huge estimates cannot overflow
+fn points(story: &Story) -> u64 {
+    story
+        .points
+        .map_or(0, u64::from)
+}
+
+fn end_of_day(day: NaiveDate) -> DateTime<Utc> {
+    day.and_time(NaiveTime::MIN)
+        .and_utc()
+        + Days::new(1)
+}
+
+fn burndown(
+    title: String,
+    db_state: &DBState,
+    story_ids: &[u32],
+    start: NaiveDate,
+    end: NaiveDate,
+    today: NaiveDate,
+    ideal: bool,
+) -> Burndown {
+    let stories: Vec<&Story> = story_ids
+        .iter()
+        .filter_map(|id| {
+            db_state
+                .stories
+                .get(id)
+        })
+        .collect();
+    let days: Vec<NaiveDate> = start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .collect();
+    let remaining = days
+        .iter()
+        .map(|day| {
+            (*day <= today).then(|| {
+                let at = end_of_day(*day);
+                stories
+                    .iter()
+                    .filter(|story| {
+                        story
+                            .created_at
+                            .is_none_or(|created_at| created_at < at)
+                            && !story
+                                .status_at(at)
+                                .is_done()
+                    })
+                    .map(|story| points(story))
+                    .sum()
+            })
+        })
+        .collect();
+    Burndown {
+        title,
+        days,
+        remaining,
+        total: stories
+            .iter()
+            .map(|story| points(story))
+            .sum(),
+        ideal,
+    }
+}
+
+/// Burndown of the stories of an epic from the day the epic, or its oldest
+/// story, was created until `today`.
+pub fn epic_burndown(db_state: &DBState, epic_id: u32, today: NaiveDate) -> Result<Burndown> {
+    let epic = db_state
+        .epics
+        .get(&epic_id)
+        .ok_or_else(|| Error::epic_not_found(epic_id))?;
+    let start = epic
+        .stories
+        .iter()
+        .filter_map(|id| {
+            db_state
+                .stories
+                .get(id)?
+                .created_at
+        })
+        .chain(epic.created_at)
+        .map(|created_at| created_at.date_naive())
+        .min()
+        .unwrap_or(today)
+        .min(today);
+    Ok(burndown(
+        format!("Epic {}: {}", epic_id, epic.name),
+        db_state,
+        &epic.stories,
+        start,
+        today,
+        today,
+        false,
+    ))
+}
+
+/// Burndown of a sprint over its planned dates, falling back to the days it
+/// was started and completed.
+pub fn sprint_burndown(db_state: &DBState, sprint_id: u32, today: NaiveDate) -> Result<Burndown> {
+    let sprint = db_state
+        .sprints
+        .get(&sprint_id)
+        .ok_or_else(|| Error::sprint_not_found(sprint_id))?;
+    let start = sprint
+        .start
+        .or(sprint
+            .started_at
+            .map(|at| at.date_naive()))
+        .unwrap_or(today);
+    let end = sprint
+        .end
+        .or(sprint
+            .completed_at
+            .map(|at| at.date_naive()))
+        .unwrap_or(today)
+        .max(start);
+    Ok(burndown(
+        format!("Sprint {}: {}", sprint_id, sprint.name),
+        db_state,
+        &sprint.stories,
+        start,
+        end,
+        today,
+        sprint
+            .end
+            .is_some(),
+    ))
+}
+
+/// Committed and completed points of the last `count` completed sprints,
+/// oldest first; a story counts as completed if it was done when its sprint
+/// was completed.
+pub fn velocity(db_state: &DBState, count: usize) -> Vec<VelocityBar> {
+    let mut sprints: Vec<(&u32, _)> = db_state
+        .sprints
+        .iter()
+        .filter(|(_, sprint)| sprint.state == SprintState::Completed)
+        .collect();
+    sprints.sort_by_key(|(id, sprint)| (sprint.completed_at, **id));
+
+    let skip = sprints
+        .len()
+        .saturating_sub(count);
+    sprints
+        .into_iter()
+        .skip(skip)
+        .map(|(id, sprint)| {
+            let stories = sprint
+                .stories
+                .iter()
+                .filter_map(|id| {
+                    db_state
+                        .stories
+                        .get(id)
+                });
+            let completed_at = sprint
+                .completed_at
+                .unwrap_or_else(Utc::now);
+            VelocityBar {
+                sprint_id: *id,
+                name: sprint
+                    .name
+                    .clone(),
+                committed: stories
+                    .clone()
+                    .map(points)
+                    .sum(),
+                completed: stories
+                    .filter(|story| {
+                        story
+                            .status_at(completed_at)
+                            .is_done()
+                    })
+                    .map(points)
+                    .sum(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Epic, Sprint, Status, StatusChange};
+
+    fn at(day: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn story(points: u32, created: u32, resolved: Option<u32>) -> Story {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        story.points = Some(points);
+        story.created_at = Some(at(created));
+        if let Some(day) = resolved {
+            story.status = Status::Resolved;
+            story.history = vec![StatusChange {
+                status: Status::Resolved,
+                at: at(day),
+            }];
+        }
+        story
+    }
+
+    fn board() -> DBState {
+        let mut db_state = DBState::default();
+        db_state
+            .stories
+            .insert(2, story(3, 1, Some(2)));
+        db_state
+            .stories
+            .insert(3, story(5, 1, None));
+        db_state
+            .stories
+            .insert(4, story(2, 3, Some(3)));
+        let mut epic = Epic::new("epic".to_owned(), "".to_owned());
+        epic.created_at = Some(at(1));
+        epic.stories = vec![2, 3, 4];
+        db_state
+            .epics
+            .insert(1, epic);
+        db_state
+    }
+
+    #[test]
+    fn epic_burndown_should_sum_huge_estimates_without_overflowing() {
+        let mut db_state = board();
+        for id in [2, 3] {
+            db_state
+                .stories
+                .insert(id, story(u32::MAX, 1, None));
+        }
+        let today = NaiveDate::from_ymd_opt(2024, 1, 4).unwrap();
+
+        let burndown = epic_burndown(&db_state, 1, today).unwrap();
+
+        assert_eq!(burndown.total, 2 * u64::from(u32::MAX) + 2);
+    }
+
+    #[test]
+    fn epic_burndown_should_follow_status_history() {
+        let db_state = board();
+        let today = NaiveDate::from_ymd_opt(2024, 1, 4).unwrap();
+
+        let burndown = epic_burndown(&db_state, 1, today).unwrap();
+
+        assert_eq!(burndown.total, 10);
+        assert_eq!(
+            burndown
+                .days
+                .len(),
+            4
+        );
+        // story 4 is added and finished on day 3
+        assert_eq!(burndown.remaining, [Some(8), Some(5), Some(5), Some(5)]);
+        assert_eq!(burndown.ideal_at(0), None);
+    }
+
+    #[test]
+    fn sprint_burndown_should_span_the_planned_dates() {
+        let mut db_state = board();
+        let mut sprint = Sprint::new(
+            "sprint".to_owned(),
+            "".to_owned(),
+            NaiveDate::from_ymd_opt(2024, 1, 1),
+            NaiveDate::from_ymd_opt(2024, 1, 5),
+        );
+        sprint.stories = vec![2, 3];
+        db_state
+            .sprints
+            .insert(5, sprint);
+        let today = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+
+        let burndown = sprint_burndown(&db_state, 5, today).unwrap();
+
+        assert_eq!(burndown.remaining, [Some(8), Some(5), None, None, None]);
+        assert_eq!(burndown.ideal_at(0), Some(8.0));
+        assert_eq!(burndown.ideal_at(4), Some(0.0));
+        assert!(matches!(
+            sprint_burndown(&db_state, 999, today),
+            Err(Error::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn velocity_should_count_points_done_by_completion() {
+        let mut db_state = board();
+        for (id, completed) in [(6, 2), (7, 4)] {
+            let mut sprint = Sprint::new(format!("sprint {}", id), "".to_owned(), None, None);
+            sprint.stories = vec![2, 3, 4];
+            sprint.state = SprintState::Completed;
+            sprint.completed_at = Some(at(completed));
+            db_state
+                .sprints
+                .insert(id, sprint);
+        }
+        db_state
+            .sprints
+            .insert(
+                8,
+                Sprint::new("planned".to_owned(), "".to_owned(), None, None),
+            );
+
+        let bars = velocity(&db_state, 1);
+        assert_eq!(
+            bars,
+            [VelocityBar {
+                sprint_id: 7,
+                name: "sprint 7".to_owned(),
+                committed: 10,
+                completed: 5,
+            }]
+        );
+        assert_eq!(velocity(&db_state, VELOCITY_SPRINTS)[0].completed, 3);
+    }
+}
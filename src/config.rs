@@ -0,0 +1,300 @@
+//! Optional settings read from `./data/config.json`.
+//!
+//! Every field has a default, so the file may be missing or only set the
+//! values a team wants to change:
+//!
+//! ```json
+//! { "theme": "high-contrast", "colors": { "closed": "white" } }
+//! ```
+
+use std::fs;
+use std::io::{self, IsTerminal};
+
+use crossterm::style::Color;
+use serde::Deserialize;
+
+use crate::dates::today;
+use crate::error::{Error, Result};
+use crate::fields::{validate_defs, FieldDef};
+use crate::models::Status;
+use crate::templates::{validate_templates, Template};
+use crate::ui::Tone;
+
+/// Rows shown per page on list pages unless the config says otherwise.
+pub const DEFAULT_PAGE_SIZE: usize = 20;
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub theme: ThemeName,
+    /// Per-tone overrides applied on top of the theme.
+    pub colors: Palette,
+    /// Rows per page on the epic and story lists.
+    pub page_size: usize,
+    /// Name worklogs are recorded under; defaults to `$USER`.
+    pub user: Option<String>,
+    /// Custom fields of epics and stories.
+    pub fields: Vec<FieldDef>,
+    /// Templates offered when creating an epic or story.
+    pub templates: Vec<Template>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            theme: ThemeName::default(),
+            colors: Palette::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+            user: None,
+            fields: vec![],
+            templates: vec![],
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config at `path`, falling back to the defaults when the
+    /// file does not exist.
+    pub fn load(path: &str) -> Result<Config> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(error) => {
+                return Err(Error::Validation(format!(
+                    "could not read config {}: {}",
+                    path, error
+                )))
+            }
+        };
+        let config: Config = serde_json::from_str(&contents)
+            .map_err(|error| Error::Validation(format!("invalid config {}: {}", path, error)))?;
+        if config.page_size == 0 {
+            return Err(Error::Validation(format!(
+                "invalid config {}: page_size must be at least 1",
+                path
+            )));
+        }
+        validate_defs(&config.fields)
+            .and_then(|()| validate_templates(&config.templates, &config.fields, today()))
+            .map_err(|error| Error::Validation(format!("invalid config {}: {}", path, error)))?;
+        Ok(config)
+    }
+
+    /// Name to record worklogs under: the configured user, else the login
+    /// name from the environment.
+    pub fn user_name(&self) -> String {
+        self.user
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .filter(|user| {
+                !user
+                    .trim()
+                    .is_empty()
+            })
+            .unwrap_or_else(|| "unknown".to_owned())
+    }
+
+    /// Colors to render with; empty when colors are turned off.
+    pub fn palette(&self) -> Palette {
+        if !colors_enabled() {
+            return Palette::default();
+        }
+        self.theme
+            .palette()
+            .merge(&self.colors)
+    }
+}
+
+/// Built-in palettes.
+#[derive(Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    HighContrast,
+    Monochrome,
+}
+
+impl ThemeName {
+    pub fn palette(self) -> Palette {
+        match self {
+            ThemeName::Default => Palette {
+                open: Some(Color::Blue),
+                in_progress: Some(Color::Yellow),
+                resolved: Some(Color::Green),
+                closed: Some(Color::DarkGrey),
+                alert: Some(Color::Red),
+                heading: Some(Color::Magenta),
+                code: Some(Color::DarkCyan),
+            },
+            ThemeName::HighContrast => Palette {
+                open: Some(Color::Cyan),
+                in_progress: Some(Color::Yellow),
+                resolved: Some(Color::Green),
+                closed: Some(Color::White),
+                alert: Some(Color::Magenta),
+                heading: Some(Color::White),
+                code: Some(Color::Yellow),
+            },
+            ThemeName::Monochrome => Palette::default(),
+        }
+    }
+}
+
+/// Foreground color per [`Tone`]; `None` leaves the terminal default.
+#[derive(Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct Palette {
+    pub open: Option<Color>,
+    pub in_progress: Option<Color>,
+    pub resolved: Option<Color>,
+    pub closed: Option<Color>,
+    pub alert: Option<Color>,
+    pub heading: Option<Color>,
+    pub code: Option<Color>,
+}
+
+impl Palette {
+    pub fn color(&self, tone: Tone) -> Option<Color> {
+        match tone {
+            Tone::Status(Status::Open) => self.open,
+            Tone::Status(Status::InProgress) => self.in_progress,
+            Tone::Status(Status::Resolved) => self.resolved,
+            Tone::Status(Status::Closed) => self.closed,
+            Tone::Alert => self.alert,
+            Tone::Heading => self.heading,
+            Tone::Code => self.code,
+        }
+    }
+
+    fn merge(self, overrides: &Palette) -> Palette {
+        Palette {
+            open: overrides
+                .open
+                .or(self.open),
+            in_progress: overrides
+                .in_progress
+                .or(self.in_progress),
+            resolved: overrides
+                .resolved
+                .or(self.resolved),
+            closed: overrides
+                .closed
+                .or(self.closed),
+            alert: overrides
+                .alert
+                .or(self.alert),
+            heading: overrides
+                .heading
+                .or(self.heading),
+            code: overrides
+                .code
+                .or(self.code),
+        }
+    }
+}
+
+// https://no-color.org: any non-empty NO_COLOR turns colors off, and so does
+// output that is not a terminal
+fn colors_enabled() -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && io::stdout().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn load_should_default_when_file_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .join("config.json");
+
+        let config = Config::load(
+            path.to_str()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn load_should_name_the_config_file_when_it_cannot_be_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir
+            .path()
+            .to_str()
+            .unwrap();
+
+        let error = Config::load(path).unwrap_err();
+        assert!(matches!(error, Error::Validation(_)));
+        assert!(error
+            .to_string()
+            .starts_with(&format!("could not read config {}: ", path)));
+    }
+
+    #[test]
+    fn load_should_merge_overrides_into_theme() {
+        let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            tmpfile,
+            r#"{{ "theme": "high-contrast", "colors": {{ "closed": "dark_grey" }} }}"#
+        )
+        .unwrap();
+
+        let config = Config::load(
+            tmpfile
+                .path()
+                .to_str()
+                .unwrap(),
+        )
+        .unwrap();
+        let palette = config
+            .theme
+            .palette()
+            .merge(&config.colors);
+
+        assert_eq!(config.theme, ThemeName::HighContrast);
+        assert_eq!(
+            palette.color(Tone::Status(Status::Closed)),
+            Some(Color::DarkGrey)
+        );
+        assert_eq!(palette.color(Tone::Status(Status::Open)), Some(Color::Cyan));
+    }
+
+    #[test]
+    fn load_should_reject_invalid_values() {
+        for contents in [
+            r#"{ "theme": "neon" }"#,
+            r#"{ "page_size": 0 }"#,
+            r#"{ "fields": [{ "name": "env", "type": "enum" }] }"#,
+            r#"{ "templates": [{ "name": "spike", "fields": { "env": "qa" } }] }"#,
+        ] {
+            let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+            write!(tmpfile, "{}", contents).unwrap();
+
+            let result = Config::load(
+                tmpfile
+                    .path()
+                    .to_str()
+                    .unwrap(),
+            );
+            assert!(matches!(result, Err(Error::Validation(_))));
+        }
+    }
+
+    #[test]
+    fn user_name_should_prefer_the_configured_user() {
+        let config = Config {
+            user: Some("alice".to_owned()),
+            ..Config::default()
+        };
+
+        assert_eq!(config.user_name(), "alice");
+    }
+}
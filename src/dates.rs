@@ -0,0 +1,160 @@
+//! Due date parsing and the date ranges the agenda works with.
+
+use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
+
+use crate::error::{Error, Result};
+use crate::models::Status;
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// The current date in the local time zone, which due dates are written in.
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Reads a date written as `2026-11-01`, `today`, `tomorrow`, an offset
+/// such as `+3d` or `+2w`, or a weekday such as `friday` or `fri`, which
+/// means the next such day from `today` on.
+pub fn parse_date(text: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let text = text
+        .trim()
+        .to_lowercase();
+    let invalid = || {
+        Error::Validation(format!(
+            "unknown date `{}`, use YYYY-MM-DD, today, tomorrow, +3d, +2w or a weekday",
+            text
+        ))
+    };
+
+    if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    match text.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Days::new(1)),
+        _ => {}
+    }
+    if let Some(offset) = text.strip_prefix('+') {
+        let (count, days_per_unit) = match (offset.strip_suffix('d'), offset.strip_suffix('w')) {
+            (Some(count), _) => (count, 1),
+            (_, Some(count)) => (count, 7),
+            _ => return Err(invalid()),
+        };
+        let days = count
+            .parse::<u64>()
+            .ok()
+            .and_then(|count| count.checked_mul(days_per_unit))
+            .ok_or_else(invalid)?;
+        return today
+            .checked_add_days(Days::new(days))
+            .ok_or_else(invalid);
+    }
+    let weekday = WEEKDAYS
+        .iter()
+        .find(|weekday| text.len() >= 3 && full_name(**weekday).starts_with(&text))
+        .ok_or_else(invalid)?;
+    let ahead = (weekday.num_days_from_monday() + 7
+        - today
+            .weekday()
+            .num_days_from_monday())
+        % 7;
+    Ok(today + Days::new(ahead as u64))
+}
+
+fn full_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// The Sunday that ends the week of `today`.
+pub fn end_of_week(today: NaiveDate) -> NaiveDate {
+    today
+        + Days::new(
+            6 - today
+                .weekday()
+                .num_days_from_monday() as u64,
+        )
+}
+
+/// Whether an item with this due date and status is late on `today`; done
+/// items are never overdue.
+pub fn is_overdue(due: Option<NaiveDate>, status: Status, today: NaiveDate) -> bool {
+    due.is_some_and(|due| due < today) && !status.is_done()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a Wednesday
+    fn wednesday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn parse_date_should_accept_natural_forms() {
+        assert_eq!(
+            parse_date("2026-11-01", wednesday()).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+        assert_eq!(parse_date(" Today ", wednesday()).unwrap(), date(14));
+        assert_eq!(parse_date("tomorrow", wednesday()).unwrap(), date(15));
+        assert_eq!(parse_date("+3d", wednesday()).unwrap(), date(17));
+        assert_eq!(parse_date("+2w", wednesday()).unwrap(), date(28));
+        assert_eq!(parse_date("friday", wednesday()).unwrap(), date(16));
+        assert_eq!(parse_date("Fri", wednesday()).unwrap(), date(16));
+        assert_eq!(parse_date("wednesday", wednesday()).unwrap(), date(14));
+        assert_eq!(parse_date("mon", wednesday()).unwrap(), date(19));
+    }
+
+    #[test]
+    fn parse_date_should_reject_unknown_forms() {
+        for text in [
+            "",
+            "soon",
+            "+3",
+            "+xd",
+            "+3m",
+            "+3é",
+            "+é",
+            "+3000000000000000000w",
+            "+99999999999999999999d",
+            "fr",
+            "2026-13-01",
+        ] {
+            assert!(
+                matches!(parse_date(text, wednesday()), Err(Error::Validation(_))),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn end_of_week_and_overdue_should_use_today() {
+        assert_eq!(end_of_week(wednesday()), date(18));
+        assert_eq!(end_of_week(date(18)), date(18));
+        assert!(is_overdue(Some(date(13)), Status::InProgress, wednesday()));
+        assert!(!is_overdue(Some(date(13)), Status::Closed, wednesday()));
+        assert!(!is_overdue(Some(date(14)), Status::Open, wednesday()));
+        assert!(!is_overdue(None, Status::Open, wednesday()));
+    }
+}
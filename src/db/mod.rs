@@ -1,138 +1,998 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs;
 
-use crate::models::{DBState, Epic, Status, Story};
-use anyhow::{anyhow, Error, Ok, Result};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
 
+use crate::error::{Error, Result};
+use crate::models::{
+    DBState, Epic, ItemKey, Link, LinkKind, Priority, Project, Sprint, SprintState, Status,
+    StatusChange, Story, SubTask, Timer, Worklog,
+};
+
+/// Storage backend for the whole board.
+///
+/// Every [`JiraHandle`] call reads the full [`DBState`], changes it and writes
+/// it back, so implementations only need to load and persist a snapshot.
 pub trait Database {
     fn read_db(&self) -> Result<DBState>;
     fn write_db(&self, db_state: &DBState) -> Result<()>;
 }
 
-// need struct to handle CRUD operation
+/// CRUD operations on epics and stories on top of a [`Database`].
 pub struct JiraHandle {
     pub database: Box<dyn Database>,
 }
 
 impl JiraHandle {
+    /// Opens the board stored in the JSON file at `file_path`.
     pub fn new(file_path: String) -> Self {
         let db = JSONFileDatabase::new(file_path);
-        JiraHandle {
-            database: Box::new(db),
-        }
+        JiraHandle::with_database(Box::new(db))
     }
 
+    /// Uses a custom storage backend instead of a JSON file.
+    pub fn with_database(database: Box<dyn Database>) -> Self {
+        JiraHandle { database }
+    }
+
+    /// Returns a snapshot of the whole board.
     pub fn read_full_record(&self) -> Result<DBState> {
         self.database
             .read_db()
     }
 
+    /// Stores a new epic and returns its id.
     pub fn create_epic(&self, epic: Epic) -> Result<u32> {
+        validate_name(Some(&epic.name))?;
         let mut db_state = self.read_full_record()?;
         db_state
             .epics
-            .insert(db_state.last_item_id + 1, epic)
-            .ok_or_else(|| anyhow::Error::msg("error creating epic"));
+            .insert(db_state.last_item_id + 1, epic);
         db_state.last_item_id += 1;
         self.database
-            .write_db(&db_state);
+            .write_db(&db_state)?;
         Ok(db_state.last_item_id)
     }
 
-    pub fn create_story(&self, story: Story, epic_id: u32) -> Result<u32> {
+    /// Stores a new epic in the project `project` with the project's next
+    /// key and returns its id.
+    pub fn create_project_epic(&self, project: &str, mut epic: Epic) -> Result<u32> {
+        validate_name(Some(&epic.name))?;
         let mut db_state = self.read_full_record()?;
+        epic.key = Some(next_key(&mut db_state, project)?);
         let new_id = db_state.last_item_id + 1;
-        db_state
-            .stories
-            .insert(new_id, story)
-            .ok_or_else(|| anyhow!("error while creating story"));
         db_state
+            .epics
+            .insert(new_id, epic);
+        db_state.last_item_id = new_id;
+        self.database
+            .write_db(&db_state)?;
+        Ok(new_id)
+    }
+
+    /// Stores a new story under the epic `epic_id` and returns the story id;
+    /// stories of an epic in a project get the project's next key.
+    pub fn create_story(&self, mut story: Story, epic_id: u32) -> Result<u32> {
+        validate_name(Some(&story.name))?;
+        let mut db_state = self.read_full_record()?;
+        let new_id = db_state.last_item_id + 1;
+        let epic = db_state
             .epics
             .get_mut(&epic_id)
-            .ok_or_else(|| anyhow!("could not find epic in database"))?
-            .stories
+            .ok_or_else(|| Error::epic_not_found(epic_id))?;
+        epic.stories
             .push(new_id);
+        if let Some(key) = epic
+            .key
+            .clone()
+        {
+            story.key = Some(next_key(&mut db_state, &key.project)?);
+        }
+        db_state
+            .stories
+            .insert(new_id, story);
         db_state.last_item_id += 1;
         self.database
-            .write_db(&db_state);
+            .write_db(&db_state)?;
         Ok(new_id)
     }
 
-    pub fn delete_epic(&self, epic_id: u32) -> Result<()> {
+    /// Stores a new story in the backlog, outside of any epic, and returns
+    /// its id.
+    pub fn create_backlog_story(&self, story: Story) -> Result<u32> {
+        validate_name(Some(&story.name))?;
         let mut db_state = self.read_full_record()?;
-        for story_id in &db_state
-            .epics
-            .get(&epic_id)
-            .ok_or_else(|| anyhow!("error in finding epic {epic_id} in database"))?
+        let new_id = db_state.last_item_id + 1;
+        db_state
+            .stories
+            .insert(new_id, story);
+        db_state.last_item_id = new_id;
+        self.database
+            .write_db(&db_state)?;
+        Ok(new_id)
+    }
+
+    /// Stores a new story in the backlog of the project `project` with the
+    /// project's next key and returns its id.
+    pub fn create_project_backlog_story(&self, project: &str, mut story: Story) -> Result<u32> {
+        validate_name(Some(&story.name))?;
+        let mut db_state = self.read_full_record()?;
+        story.key = Some(next_key(&mut db_state, project)?);
+        let new_id = db_state.last_item_id + 1;
+        db_state
             .stories
+            .insert(new_id, story);
+        db_state.last_item_id = new_id;
+        self.database
+            .write_db(&db_state)?;
+        Ok(new_id)
+    }
+
+    /// Adds a project under `key`, which has to be 2 to 10 uppercase letters
+    /// or digits starting with a letter, e.g. `API`.
+    pub fn create_project(&self, key: &str, name: String) -> Result<()> {
+        validate_project_key(key)?;
+        validate_name(Some(&name))?;
+        let mut db_state = self.read_full_record()?;
+        if db_state
+            .projects
+            .contains_key(key)
         {
-            db_state
-                .stories
-                .remove(story_id);
+            return Err(Error::Conflict(format!("project {key} already exists")));
         }
         db_state
+            .projects
+            .insert(key.to_owned(), Project::new(name));
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Moves a backlog story under the epic `epic_id`; a story without a key
+    /// gets the next key of the epic's project, while a story keyed in a
+    /// project only moves under an epic of the same project.
+    pub fn attach_story(&self, story_id: u32, epic_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        backlog_story(&db_state, story_id)?;
+        let epic_project = db_state
+            .epics
+            .get(&epic_id)
+            .ok_or_else(|| Error::epic_not_found(epic_id))?
+            .key
+            .as_ref()
+            .map(|key| {
+                key.project
+                    .clone()
+            });
+        match (&db_state.stories[&story_id].key, epic_project) {
+            (Some(story_key), epic_project)
+                if epic_project.as_deref()
+                    != Some(
+                        story_key
+                            .project
+                            .as_str(),
+                    ) =>
+            {
+                return Err(Error::Conflict(format!(
+                    "story {story_key} belongs to project {} and cannot move to epic {epic_id}",
+                    story_key.project
+                )));
+            }
+            (None, Some(project)) => {
+                let key = next_key(&mut db_state, &project)?;
+                if let Some(story) = db_state
+                    .stories
+                    .get_mut(&story_id)
+                {
+                    story.key = Some(key);
+                }
+            }
+            _ => {}
+        }
+        if let Some(epic) = db_state
+            .epics
+            .get_mut(&epic_id)
+        {
+            epic.stories
+                .push(story_id);
+        }
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Deletes an epic together with all of its stories and their sub-tasks.
+    pub fn delete_epic(&self, epic_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let epic = db_state
             .epics
             .remove(&epic_id)
-            .ok_or_else(|| anyhow::Error::msg("error while deleting epic"));
+            .ok_or_else(|| Error::epic_not_found(epic_id))?;
+        for story_id in epic.stories {
+            remove_story(&mut db_state, story_id);
+        }
         self.database
-            .write_db(&db_state);
+            .write_db(&db_state)?;
         Ok(())
     }
 
+    /// Deletes a story with its sub-tasks and removes it from its epic.
     pub fn delete_story(&self, epic_id: u32, story_id: u32) -> Result<()> {
         let mut db_state = self.read_full_record()?;
+        if !db_state
+            .stories
+            .contains_key(&story_id)
+        {
+            return Err(Error::story_not_found(story_id));
+        }
         let epic_mut = db_state
             .epics
             .get_mut(&epic_id)
-            .ok_or_else(|| anyhow!("could not find epic with id {epic_id}"))?;
+            .ok_or_else(|| Error::epic_not_found(epic_id))?;
         let story_index = epic_mut
             .stories
             .iter()
             .position(|id| id == &story_id)
-            .ok_or_else(|| anyhow!("story is not found in epic stories vector"))?;
+            .ok_or_else(|| {
+                Error::Conflict(format!(
+                    "story {story_id} does not belong to epic {epic_id}"
+                ))
+            })?;
         //remove the story id from epic object
         epic_mut
             .stories
             .remove(story_index);
         // remove story object form story hashmap
+        remove_story(&mut db_state, story_id);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Deletes a backlog story with its sub-tasks.
+    pub fn delete_backlog_story(&self, story_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        backlog_story(&db_state, story_id)?;
+        remove_story(&mut db_state, story_id);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Stores a new sub-task under the story `story_id` and returns its id.
+    pub fn create_subtask(&self, subtask: SubTask, story_id: u32) -> Result<u32> {
+        validate_name(Some(&subtask.name))?;
+        let mut db_state = self.read_full_record()?;
+        let new_id = db_state.last_item_id + 1;
         db_state
             .stories
-            .remove(&story_id);
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?
+            .subtasks
+            .push(new_id);
+        db_state
+            .subtasks
+            .insert(new_id, subtask);
+        db_state.last_item_id = new_id;
         self.database
-            .write_db(&db_state);
+            .write_db(&db_state)?;
+        Ok(new_id)
+    }
+
+    /// Marks an open sub-task as resolved and a done one as open again, and
+    /// returns the new status.
+    pub fn toggle_subtask(&self, subtask_id: u32) -> Result<Status> {
+        let mut db_state = self.read_full_record()?;
+        let subtask = db_state
+            .subtasks
+            .get_mut(&subtask_id)
+            .ok_or_else(|| Error::subtask_not_found(subtask_id))?;
+        subtask.status = if subtask
+            .status
+            .is_done()
+        {
+            Status::Open
+        } else {
+            Status::Resolved
+        };
+        let status = subtask.status;
+        self.database
+            .write_db(&db_state)?;
+        Ok(status)
+    }
+
+    /// Deletes a sub-task and removes it from its story.
+    pub fn delete_subtask(&self, story_id: u32, subtask_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let story = db_state
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?;
+        let index = story
+            .subtasks
+            .iter()
+            .position(|id| id == &subtask_id)
+            .ok_or_else(|| {
+                Error::Conflict(format!(
+                    "sub-task {subtask_id} does not belong to story {story_id}"
+                ))
+            })?;
+        story
+            .subtasks
+            .remove(index);
+        db_state
+            .subtasks
+            .remove(&subtask_id);
+        self.database
+            .write_db(&db_state)?;
         Ok(())
     }
 
+    /// Stores a new planned sprint and returns its id.
+    pub fn create_sprint(&self, sprint: Sprint) -> Result<u32> {
+        validate_name(Some(&sprint.name))?;
+        if let (Some(start), Some(end)) = (sprint.start, sprint.end) {
+            if end < start {
+                return Err(Error::Validation(
+                    "sprint cannot end before it starts".to_owned(),
+                ));
+            }
+        }
+        let mut db_state = self.read_full_record()?;
+        let new_id = db_state.last_item_id + 1;
+        db_state
+            .sprints
+            .insert(new_id, sprint);
+        db_state.last_item_id = new_id;
+        self.database
+            .write_db(&db_state)?;
+        Ok(new_id)
+    }
+
+    /// Plans a story into a sprint that has not been completed yet; a story
+    /// can only be part of one such sprint at a time.
+    pub fn add_story_to_sprint(&self, sprint_id: u32, story_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        if !db_state
+            .stories
+            .contains_key(&story_id)
+        {
+            return Err(Error::story_not_found(story_id));
+        }
+        if let Some(other_id) = db_state.open_sprint_of_story(story_id) {
+            return Err(Error::Conflict(format!(
+                "story {story_id} is already planned into sprint {other_id}"
+            )));
+        }
+        open_sprint(&mut db_state, sprint_id)?
+            .stories
+            .push(story_id);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Takes a story back out of a sprint that has not been completed yet.
+    pub fn remove_story_from_sprint(&self, sprint_id: u32, story_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let sprint = open_sprint(&mut db_state, sprint_id)?;
+        let index = sprint
+            .stories
+            .iter()
+            .position(|id| id == &story_id)
+            .ok_or_else(|| {
+                Error::Conflict(format!(
+                    "story {story_id} is not part of sprint {sprint_id}"
+                ))
+            })?;
+        sprint
+            .stories
+            .remove(index);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Starts a planned sprint; fails while another sprint is active.
+    pub fn start_sprint(&self, sprint_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        if let Some(active_id) = db_state.active_sprint() {
+            return Err(Error::Conflict(format!(
+                "sprint {active_id} is still active"
+            )));
+        }
+        let sprint = db_state
+            .sprints
+            .get_mut(&sprint_id)
+            .ok_or_else(|| Error::sprint_not_found(sprint_id))?;
+        if sprint.state != SprintState::Planned {
+            return Err(Error::Conflict(format!(
+                "sprint {sprint_id} has already been started"
+            )));
+        }
+        sprint.state = SprintState::Active;
+        sprint.started_at = Some(Utc::now());
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Completes the active sprint and returns the ids of its stories that
+    /// are not done yet, so they can be rolled into the next sprint.
+    pub fn complete_sprint(&self, sprint_id: u32) -> Result<Vec<u32>> {
+        let mut db_state = self.read_full_record()?;
+        let sprint = db_state
+            .sprints
+            .get_mut(&sprint_id)
+            .ok_or_else(|| Error::sprint_not_found(sprint_id))?;
+        if sprint.state != SprintState::Active {
+            return Err(Error::Conflict(format!("sprint {sprint_id} is not active")));
+        }
+        sprint.state = SprintState::Completed;
+        sprint.completed_at = Some(Utc::now());
+        let unfinished = sprint
+            .stories
+            .iter()
+            .copied()
+            .filter(|id| {
+                db_state
+                    .stories
+                    .get(id)
+                    .is_some_and(|story| {
+                        !story
+                            .status
+                            .is_done()
+                    })
+            })
+            .collect();
+        self.database
+            .write_db(&db_state)?;
+        Ok(unfinished)
+    }
+
+    /// Plans the unfinished stories of the completed sprint `from` into the
+    /// sprint `to`; the completed sprint keeps them as a record.
+    pub fn roll_over_stories(&self, from: u32, to: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let sprint = db_state
+            .sprints
+            .get(&from)
+            .ok_or_else(|| Error::sprint_not_found(from))?;
+        if sprint.state != SprintState::Completed {
+            return Err(Error::Conflict(format!(
+                "sprint {from} has not been completed"
+            )));
+        }
+        let unfinished: Vec<u32> = sprint
+            .stories
+            .iter()
+            .copied()
+            .filter(|id| {
+                db_state
+                    .stories
+                    .get(id)
+                    .is_some_and(|story| {
+                        !story
+                            .status
+                            .is_done()
+                    })
+                    && db_state
+                        .open_sprint_of_story(*id)
+                        .is_none()
+            })
+            .collect();
+        open_sprint(&mut db_state, to)?
+            .stories
+            .extend(unfinished);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Links two stories, e.g. `from` blocks `to`; blocking and duplicate
+    /// links may not form a cycle.
+    pub fn link_stories(&self, from: u32, kind: LinkKind, to: u32) -> Result<()> {
+        let (from, kind, to) = match kind {
+            LinkKind::IsBlockedBy => (to, LinkKind::Blocks, from),
+            kind => (from, kind, to),
+        };
+        if from == to {
+            return Err(Error::Validation(
+                "a story cannot be linked to itself".to_owned(),
+            ));
+        }
+        let mut db_state = self.read_full_record()?;
+        for id in [from, to] {
+            if !db_state
+                .stories
+                .contains_key(&id)
+            {
+                return Err(Error::story_not_found(id));
+            }
+        }
+        if db_state
+            .links
+            .iter()
+            .any(|link| {
+                link.kind == kind
+                    && (link.from == from && link.to == to
+                        || kind == LinkKind::RelatesTo && link.from == to && link.to == from)
+            })
+        {
+            return Err(Error::Conflict(format!(
+                "story {from} already {kind} story {to}"
+            )));
+        }
+        if kind != LinkKind::RelatesTo {
+            if let Some(path) = link_path(&db_state, kind, to, from) {
+                let path: Vec<String> = path
+                    .iter()
+                    .chain([&to])
+                    .map(u32::to_string)
+                    .collect();
+                return Err(Error::Conflict(format!(
+                    "story {from} {kind} story {to} would create a cycle: {}",
+                    path.join(" -> ")
+                )));
+            }
+        }
+        db_state
+            .links
+            .push(Link { from, to, kind });
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Removes every link between two stories.
+    pub fn unlink_stories(&self, story_id: u32, other_id: u32) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let count = db_state
+            .links
+            .len();
+        db_state
+            .links
+            .retain(|link| {
+                !(link.from == story_id && link.to == other_id
+                    || link.from == other_id && link.to == story_id)
+            });
+        if db_state
+            .links
+            .len()
+            == count
+        {
+            return Err(Error::Conflict(format!(
+                "story {story_id} is not linked to story {other_id}"
+            )));
+        }
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Starts a timer for `user` on a story; a timer the user still has
+    /// running on another story is stopped and logged first.
+    pub fn start_timer(&self, story_id: u32, user: &str) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        if !db_state
+            .stories
+            .contains_key(&story_id)
+        {
+            return Err(Error::story_not_found(story_id));
+        }
+        let now = Utc::now();
+        if let Some(timer) = db_state
+            .timers
+            .remove(user)
+        {
+            if timer.story_id == story_id {
+                return Err(Error::Conflict(format!(
+                    "{user} already has a timer running on story {story_id}"
+                )));
+            }
+            log_timer(&mut db_state, user, timer, now, String::new());
+        }
+        db_state
+            .timers
+            .insert(
+                user.to_owned(),
+                Timer {
+                    story_id,
+                    started_at: now,
+                },
+            );
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Stops the running timer of `user` and logs the time on its story.
+    pub fn stop_timer(&self, user: &str, note: String) -> Result<Worklog> {
+        let mut db_state = self.read_full_record()?;
+        let timer = db_state
+            .timers
+            .remove(user)
+            .ok_or_else(|| Error::Conflict(format!("{user} has no timer running")))?;
+        let worklog = log_timer(&mut db_state, user, timer, Utc::now(), note);
+        self.database
+            .write_db(&db_state)?;
+        Ok(worklog)
+    }
+
+    /// Changes the status of an epic; its stories keep their own status.
     pub fn update_epic_status(&self, epic_id: u32, status: Status) -> Result<()> {
         let mut db_state = self.read_full_record()?;
         db_state
             .epics
             .get_mut(&epic_id)
-            .ok_or_else(|| anyhow!("could not find epic with id {epic_id}"))?
+            .ok_or_else(|| Error::epic_not_found(epic_id))?
             .status = status;
         self.database
-            .write_db(&db_state);
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Changes the priority of an epic.
+    pub fn update_epic_priority(&self, epic_id: u32, priority: Priority) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        db_state
+            .epics
+            .get_mut(&epic_id)
+            .ok_or_else(|| Error::epic_not_found(epic_id))?
+            .priority = priority;
+        self.database
+            .write_db(&db_state)?;
         Ok(())
     }
+
+    /// Sets or clears the due date of an epic.
+    pub fn update_epic_due(&self, epic_id: u32, due: Option<NaiveDate>) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        db_state
+            .epics
+            .get_mut(&epic_id)
+            .ok_or_else(|| Error::epic_not_found(epic_id))?
+            .due = due;
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Sets a custom field of an epic, or removes it when `value` is
+    /// `None`; values are expected to be checked against the field
+    /// definitions by the caller.
+    pub fn update_epic_field(&self, epic_id: u32, name: &str, value: Option<String>) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let fields = &mut db_state
+            .epics
+            .get_mut(&epic_id)
+            .ok_or_else(|| Error::epic_not_found(epic_id))?
+            .fields;
+        set_field(fields, name, value);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Renames an epic, replaces its description and/or changes its status
+    /// in one write; fields left as `None` keep their current value.
+    pub fn update_epic_details(
+        &self,
+        epic_id: u32,
+        name: Option<String>,
+        description: Option<String>,
+        status: Option<Status>,
+    ) -> Result<()> {
+        validate_name(name.as_deref())?;
+        let mut db_state = self.read_full_record()?;
+        let epic = db_state
+            .epics
+            .get_mut(&epic_id)
+            .ok_or_else(|| Error::epic_not_found(epic_id))?;
+        if let Some(name) = name {
+            epic.name = name;
+        }
+        if let Some(description) = description {
+            epic.description = description;
+        }
+        if let Some(status) = status {
+            epic.status = status;
+        }
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Changes the status of a story and records the change in its history.
     pub fn update_story_status(&self, story_id: u32, status: Status) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let story = db_state
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?;
+        set_story_status(story, status);
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Changes the priority of a story.
+    pub fn update_story_priority(&self, story_id: u32, priority: Priority) -> Result<()> {
         let mut db_state = self.read_full_record()?;
         db_state
             .stories
             .get_mut(&story_id)
-            .ok_or_else(|| anyhow!("could not find story with id {story_id}"))?
-            .status = status;
+            .ok_or_else(|| Error::story_not_found(story_id))?
+            .priority = priority;
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Sets or clears the story point estimate of a story.
+    pub fn update_story_points(&self, story_id: u32, points: Option<u32>) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        db_state
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?
+            .points = points;
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Sets or clears the due date of a story.
+    pub fn update_story_due(&self, story_id: u32, due: Option<NaiveDate>) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        db_state
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?
+            .due = due;
+        self.database
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Sets a custom field of a story, or removes it when `value` is
+    /// `None`.
+    pub fn update_story_field(
+        &self,
+        story_id: u32,
+        name: &str,
+        value: Option<String>,
+    ) -> Result<()> {
+        let mut db_state = self.read_full_record()?;
+        let fields = &mut db_state
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?
+            .fields;
+        set_field(fields, name, value);
         self.database
-            .write_db(&db_state);
+            .write_db(&db_state)?;
+        Ok(())
+    }
+
+    /// Renames a story, replaces its description and/or changes its status
+    /// in one write; fields left as `None` keep their current value.
+    pub fn update_story_details(
+        &self,
+        story_id: u32,
+        name: Option<String>,
+        description: Option<String>,
+        status: Option<Status>,
+    ) -> Result<()> {
+        validate_name(name.as_deref())?;
+        let mut db_state = self.read_full_record()?;
+        let story = db_state
+            .stories
+            .get_mut(&story_id)
+            .ok_or_else(|| Error::story_not_found(story_id))?;
+        if let Some(name) = name {
+            story.name = name;
+        }
+        if let Some(description) = description {
+            story.description = description;
+        }
+        if let Some(status) = status {
+            set_story_status(story, status);
+        }
+        self.database
+            .write_db(&db_state)?;
         Ok(())
     }
 }
 
+// changes the status of a story, recording the change in its history
+fn set_story_status(story: &mut Story, status: Status) {
+    if story.status != status {
+        story
+            .history
+            .push(StatusChange {
+                status,
+                at: Utc::now(),
+            });
+    }
+    story.status = status;
+}
+
+// drops a story together with its sub-tasks and any sprint references
+fn remove_story(db_state: &mut DBState, story_id: u32) {
+    if let Some(story) = db_state
+        .stories
+        .remove(&story_id)
+    {
+        for subtask_id in &story.subtasks {
+            db_state
+                .subtasks
+                .remove(subtask_id);
+        }
+    }
+    for sprint in db_state
+        .sprints
+        .values_mut()
+    {
+        sprint
+            .stories
+            .retain(|id| id != &story_id);
+    }
+    db_state
+        .timers
+        .retain(|_, timer| timer.story_id != story_id);
+    db_state
+        .links
+        .retain(|link| link.from != story_id && link.to != story_id);
+}
+
+// ids from `from` to `to` following links of one kind, both ends included
+fn link_path(db_state: &DBState, kind: LinkKind, from: u32, to: u32) -> Option<Vec<u32>> {
+    let mut previous: HashMap<u32, u32> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(id) = queue.pop_front() {
+        if id == to {
+            let mut path = vec![to];
+            while let Some(id) = previous.get(path.last()?) {
+                path.push(*id);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for link in db_state
+            .links
+            .iter()
+            .filter(|link| link.kind == kind && link.from == id)
+        {
+            if link.to != from && !previous.contains_key(&link.to) {
+                previous.insert(link.to, id);
+                queue.push_back(link.to);
+            }
+        }
+    }
+    None
+}
+
+// adds the time since the timer was started to its story, rounded up to
+// whole minutes
+fn log_timer(
+    db_state: &mut DBState,
+    user: &str,
+    timer: Timer,
+    now: DateTime<Utc>,
+    note: String,
+) -> Worklog {
+    let seconds = (now - timer.started_at)
+        .num_seconds()
+        .max(0) as u64;
+    let worklog = Worklog {
+        user: user.to_owned(),
+        start: timer.started_at,
+        minutes: seconds.div_ceil(60) as u32,
+        note,
+    };
+    if let Some(story) = db_state
+        .stories
+        .get_mut(&timer.story_id)
+    {
+        story
+            .worklogs
+            .push(worklog.clone());
+    }
+    worklog
+}
+
+// a sprint whose story list can still change
+fn open_sprint(db_state: &mut DBState, sprint_id: u32) -> Result<&mut Sprint> {
+    let sprint = db_state
+        .sprints
+        .get_mut(&sprint_id)
+        .ok_or_else(|| Error::sprint_not_found(sprint_id))?;
+    if sprint.state == SprintState::Completed {
+        return Err(Error::Conflict(format!(
+            "sprint {sprint_id} has already been completed"
+        )));
+    }
+    Ok(sprint)
+}
+
+// checks that the story exists and is not attached to an epic yet
+fn backlog_story(db_state: &DBState, story_id: u32) -> Result<()> {
+    if !db_state
+        .stories
+        .contains_key(&story_id)
+    {
+        return Err(Error::story_not_found(story_id));
+    }
+    match db_state.epic_of_story(story_id) {
+        Some(epic_id) => Err(Error::Conflict(format!(
+            "story {story_id} already belongs to epic {epic_id}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+// takes the next number of the project's sequence
+fn next_key(db_state: &mut DBState, project: &str) -> Result<ItemKey> {
+    let entry = db_state
+        .projects
+        .get_mut(project)
+        .ok_or_else(|| Error::ProjectNotFound(project.to_owned()))?;
+    entry.last_number += 1;
+    Ok(ItemKey {
+        project: project.to_owned(),
+        number: entry.last_number,
+    })
+}
+
+fn validate_project_key(key: &str) -> Result<()> {
+    let valid = (2..=10).contains(&key.len())
+        && key.starts_with(|c: char| c.is_ascii_uppercase())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Validation(format!(
+            "project key must be 2 to 10 uppercase letters or digits starting with a letter, not `{key}`"
+        )))
+    }
+}
+
+fn set_field(fields: &mut BTreeMap<String, String>, name: &str, value: Option<String>) {
+    match value {
+        Some(value) => {
+            fields.insert(name.to_owned(), value);
+        }
+        None => {
+            fields.remove(name);
+        }
+    }
+}
+
+fn validate_name(name: Option<&str>) -> Result<()> {
+    match name {
+        Some(name)
+            if name
+                .trim()
+                .is_empty() =>
+        {
+            Err(Error::Validation("name cannot be empty".to_owned()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// [`Database`] that keeps the board in a single JSON file.
 pub struct JSONFileDatabase {
     pub file_path: String,
 }
 
 impl JSONFileDatabase {
-    fn new(file_path: String) -> Self {
+    /// Uses the file at `file_path`, which has to exist once the board is
+    /// read.
+    pub fn new(file_path: String) -> Self {
         JSONFileDatabase { file_path }
     }
 }
@@ -153,6 +1013,7 @@ impl Database for JSONFileDatabase {
         Ok(())
     }
 }
+#[cfg(test)]
 pub mod tests {
     use super::*;
     pub mod db_tests; // tests for db reads and writes
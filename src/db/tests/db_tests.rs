@@ -1,15 +1,23 @@
 use super::*;
 mod database {
     use super::*;
-    use crate::models::{Epic, Story};
-    use std::{collections::HashMap, io::Write};
+    use crate::models::{
+        BugDetails, Epic, IssueType, ItemKey, Priority, Project, Severity, Sprint, Story, SubTask,
+        Worklog,
+    };
+    use chrono::{NaiveDate, Utc};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        io::Write,
+    };
 
     #[test]
     fn read_db_should_fail_with_invalid_path() {
         let json_file_db = JSONFileDatabase::new("Invalid path".to_owned());
-        assert!(json_file_db
-            .read_db()
-            .is_err());
+        assert!(matches!(
+            json_file_db.read_db(),
+            Err(crate::error::Error::Io(_))
+        ));
     }
     #[test]
     fn read_db_should_fail_with_invalid_json() {
@@ -24,9 +32,7 @@ mod database {
                 .expect("failed to convert tmpfile str")
                 .to_string(),
         );
-        assert!(db
-            .read_db()
-            .is_err())
+        assert!(matches!(db.read_db(), Err(crate::error::Error::Corrupt(_))))
     }
 
     #[test]
@@ -48,6 +54,26 @@ mod database {
             .is_ok())
     }
 
+    #[test]
+    fn read_db_should_default_missing_priority() {
+        let mut tmpfile = tempfile::NamedTempFile::new().unwrap();
+
+        let file_contents = r#"{ "last_item_id": 2, "epics": { "1": { "name": "epic", "description": "", "status": "Open", "stories": [2] } }, "stories": { "2": { "name": "story", "description": "", "status": "Open" } } }"#;
+        write!(tmpfile, "{}", file_contents).unwrap();
+        let db = JSONFileDatabase::new(
+            tmpfile
+                .path()
+                .to_str()
+                .unwrap()
+                .to_owned(),
+        );
+        let db_state = db
+            .read_db()
+            .unwrap();
+        assert_eq!(db_state.epics[&1].priority, Priority::Medium);
+        assert_eq!(db_state.stories[&2].priority, Priority::Medium);
+    }
+
     #[test]
     fn write_db_should_work() {
         let tmpfile = tempfile::NamedTempFile::new().unwrap();
@@ -60,24 +86,81 @@ mod database {
                 .to_string(),
         );
         let story = Story {
+            key: Some(ItemKey {
+                project: "API".to_owned(),
+                number: 2,
+            }),
             name: "epic 1".to_owned(),
             description: "description 1".to_owned(),
             status: crate::models::Status::Open,
+            priority: Priority::High,
+            created_at: None,
+            due: NaiveDate::from_ymd_opt(2024, 1, 10),
+            fields: BTreeMap::from([("environment".to_owned(), "staging".to_owned())]),
+            labels: vec!["triage".to_owned()],
+            subtasks: vec![3],
+            issue_type: IssueType::Bug,
+            bug: Some(BugDetails {
+                steps_to_reproduce: "1. open the app".to_owned(),
+                severity: Severity::Critical,
+            }),
+            points: Some(5),
+            history: vec![StatusChange {
+                status: crate::models::Status::Open,
+                at: Utc::now(),
+            }],
+            worklogs: vec![Worklog {
+                user: "alice".to_owned(),
+                start: Utc::now(),
+                minutes: 30,
+                note: "triage".to_owned(),
+            }],
         };
         let epic = Epic {
+            key: Some(ItemKey {
+                project: "API".to_owned(),
+                number: 1,
+            }),
             name: "epic 1".to_owned(),
             description: "description 1".to_owned(),
             status: crate::models::Status::Open,
+            priority: Priority::Low,
+            created_at: None,
+            due: None,
+            fields: BTreeMap::new(),
+            labels: vec![],
             stories: vec![2],
         };
         let mut stories = HashMap::new();
         stories.insert(2, story);
         let mut epics = HashMap::new();
         epics.insert(1, epic);
+        let mut subtasks = HashMap::new();
+        subtasks.insert(3, SubTask::new("subtask 1".to_owned(), None));
+        let mut sprint = Sprint::new(
+            "sprint 1".to_owned(),
+            "ship it".to_owned(),
+            NaiveDate::from_ymd_opt(2024, 1, 1),
+            NaiveDate::from_ymd_opt(2024, 1, 14),
+        );
+        sprint.stories = vec![2];
+        let mut sprints = HashMap::new();
+        sprints.insert(4, sprint);
         let db_state = DBState {
-            last_item_id: 2,
+            last_item_id: 4,
             epics,
             stories,
+            subtasks,
+            sprints,
+            timers: HashMap::new(),
+            links: vec![],
+            projects: BTreeMap::from([(
+                "API".to_owned(),
+                Project {
+                    name: "api".to_owned(),
+                    last_number: 2,
+                },
+            )]),
         };
         assert!(db
             .write_db(&db_state)
@@ -3,12 +3,14 @@ use std::borrow::Borrow;
 use test_utils::MockDB;
 
 use super::*;
+use crate::error::{Error, ItemKind};
+use chrono::NaiveDate;
 #[test]
 fn create_epic_should_work() {
     let jira_handle = JiraHandle {
         database: Box::new(MockDB::new()),
     };
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     let res = jira_handle.create_epic(epic.clone());
     assert!(res.is_ok());
     let saved_id = res.unwrap();
@@ -34,7 +36,13 @@ fn create_story_should_error_if_invalid_epic_id() {
     };
     let story = Story::new("name".to_owned(), "".to_owned());
     let res = jira_handle.create_story(story, 999);
-    assert!(res.is_err())
+    assert!(matches!(
+        res,
+        Err(Error::NotFound {
+            kind: ItemKind::Epic,
+            id: 999
+        })
+    ))
 }
 
 #[test]
@@ -43,7 +51,7 @@ fn create_story_should_work() {
         database: Box::new(MockDB::new()),
     };
     let story = Story::new("name".to_owned(), "".to_owned());
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -72,7 +80,7 @@ fn delete_epic_should_error_if_invalid_epic_id() {
     let jira_handle = JiraHandle {
         database: Box::new(MockDB::new()),
     };
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -87,7 +95,7 @@ fn delete_epic_should_work() {
     let jira_handle = JiraHandle {
         database: Box::new(MockDB::new()),
     };
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -112,7 +120,7 @@ fn delete_story_should_error_if_invalid_epic_id() {
         database: Box::new(MockDB::new()),
     };
     let story = Story::new("name".to_owned(), "".to_owned());
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epic first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -132,7 +140,7 @@ fn delete_story_should_error_if_story_not_found_in_epic() {
         database: Box::new(MockDB::new()),
     };
     let story = Story::new("name".to_owned(), "".to_owned());
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -141,7 +149,25 @@ fn delete_story_should_error_if_story_not_found_in_epic() {
     let res_story = jira_handle.create_story(story, saved_epic_id);
     assert!(res_story.is_ok());
     let res_story_queried = jira_handle.delete_story(saved_epic_id, 999);
-    assert!(res_story_queried.is_err());
+    assert!(matches!(res_story_queried, Err(Error::NotFound { .. })));
+}
+
+#[test]
+fn delete_story_should_error_if_story_belongs_to_another_epic() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let other_epic_id = jira_handle
+        .create_epic(Epic::new("other".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), other_epic_id)
+        .unwrap();
+    let res = jira_handle.delete_story(epic_id, story_id);
+    assert!(matches!(res, Err(Error::Conflict(_))));
 }
 
 #[test]
@@ -150,7 +176,7 @@ fn delete_story_should_work() {
         database: Box::new(MockDB::new()),
     };
     let story = Story::new("name".to_owned(), "".to_owned());
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -185,7 +211,7 @@ fn update_epic_status_should_error_if_invalid_epic_id() {
     let jira_handle = JiraHandle {
         database: Box::new(MockDB::new()),
     };
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -198,7 +224,7 @@ fn update_epic_status_should_work() {
     let jira_handle = JiraHandle {
         database: Box::new(MockDB::new()),
     };
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -226,7 +252,7 @@ fn update_story_status_should_work() {
         database: Box::new(MockDB::new()),
     };
     let story = Story::new("name".to_owned(), "".to_owned());
-    let epic = Epic::new("".to_owned(), "".to_owned());
+    let epic = Epic::new("epic".to_owned(), "".to_owned());
     //create epi first
     let res_epic = jira_handle.create_epic(epic);
     assert!(res_epic.is_ok());
@@ -250,3 +276,941 @@ fn update_story_status_should_work() {
         Status::Resolved
     );
 }
+
+#[test]
+fn update_epic_details_should_keep_unset_fields() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("name".to_owned(), "description".to_owned()))
+        .unwrap();
+    assert!(jira_handle
+        .update_epic_details(epic_id, Some("renamed".to_owned()), None, None)
+        .is_ok());
+    let db_state = jira_handle
+        .database
+        .read_db()
+        .unwrap();
+    let epic = db_state
+        .epics
+        .get(&epic_id)
+        .unwrap();
+    assert_eq!(epic.name, "renamed");
+    assert_eq!(epic.description, "description");
+}
+
+#[test]
+fn update_story_details_should_error_if_invalid_story_id() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    assert!(jira_handle
+        .update_story_details(999, None, Some("description".to_owned()), None)
+        .is_err());
+}
+
+#[test]
+fn update_epic_details_should_reject_empty_name() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("name".to_owned(), "".to_owned()))
+        .unwrap();
+    let res = jira_handle.update_epic_details(epic_id, Some("  ".to_owned()), None, None);
+    assert!(matches!(res, Err(Error::Validation(_))));
+}
+
+#[test]
+fn create_should_reject_empty_names() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    jira_handle
+        .create_project("API", "backend".to_owned())
+        .unwrap();
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let blank_epic = || Epic::new(" ".to_owned(), "".to_owned());
+    let blank_story = || Story::new("".to_owned(), "".to_owned());
+
+    for res in [
+        jira_handle.create_epic(blank_epic()),
+        jira_handle.create_project_epic("API", blank_epic()),
+        jira_handle.create_story(blank_story(), epic_id),
+        jira_handle.create_backlog_story(blank_story()),
+        jira_handle.create_project_backlog_story("API", blank_story()),
+    ] {
+        assert!(matches!(res, Err(Error::Validation(_))));
+    }
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.last_item_id, epic_id);
+    assert_eq!(db_state.projects["API"].last_number, 0);
+}
+
+#[test]
+fn update_priority_should_work() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+
+    jira_handle
+        .update_epic_priority(epic_id, Priority::High)
+        .unwrap();
+    jira_handle
+        .update_story_priority(story_id, Priority::Low)
+        .unwrap();
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.epics[&epic_id].priority, Priority::High);
+    assert_eq!(db_state.stories[&story_id].priority, Priority::Low);
+    assert!(matches!(
+        jira_handle.update_story_priority(999, Priority::High),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn subtasks_should_be_created_toggled_and_deleted() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+
+    let subtask_id = jira_handle
+        .create_subtask(
+            SubTask::new("write docs".to_owned(), Some("sam".to_owned())),
+            story_id,
+        )
+        .unwrap();
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.stories[&story_id].subtasks, vec![subtask_id]);
+    assert_eq!(db_state.subtasks[&subtask_id].status, Status::Open);
+
+    assert_eq!(
+        jira_handle
+            .toggle_subtask(subtask_id)
+            .unwrap(),
+        Status::Resolved
+    );
+    assert_eq!(
+        jira_handle
+            .toggle_subtask(subtask_id)
+            .unwrap(),
+        Status::Open
+    );
+
+    assert!(matches!(
+        jira_handle.delete_subtask(epic_id, subtask_id),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            ..
+        })
+    ));
+    jira_handle
+        .delete_subtask(story_id, subtask_id)
+        .unwrap();
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert!(db_state.stories[&story_id]
+        .subtasks
+        .is_empty());
+    assert!(db_state
+        .subtasks
+        .is_empty());
+}
+
+#[test]
+fn create_subtask_should_validate_input() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+
+    assert!(matches!(
+        jira_handle.create_subtask(SubTask::new("task".to_owned(), None), 7),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 7
+        })
+    ));
+    assert!(matches!(
+        jira_handle.create_subtask(SubTask::new(" ".to_owned(), None), 7),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        jira_handle.toggle_subtask(7),
+        Err(Error::NotFound {
+            kind: ItemKind::SubTask,
+            id: 7
+        })
+    ));
+}
+
+#[test]
+fn delete_story_should_delete_its_subtasks() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+    jira_handle
+        .create_subtask(SubTask::new("task".to_owned(), None), story_id)
+        .unwrap();
+
+    jira_handle
+        .delete_epic(epic_id)
+        .unwrap();
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .subtasks
+        .is_empty());
+}
+
+#[test]
+fn backlog_stories_should_be_created_attached_and_deleted() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let first = jira_handle
+        .create_backlog_story(Story::new("first".to_owned(), "".to_owned()))
+        .unwrap();
+    let second = jira_handle
+        .create_backlog_story(Story::new("second".to_owned(), "".to_owned()))
+        .unwrap();
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.backlog(), vec![first, second]);
+    assert!(db_state.epics[&epic_id]
+        .stories
+        .is_empty());
+
+    jira_handle
+        .attach_story(first, epic_id)
+        .unwrap();
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.backlog(), vec![second]);
+    assert_eq!(db_state.epics[&epic_id].stories, vec![first]);
+    assert_eq!(db_state.epic_of_story(first), Some(epic_id));
+
+    jira_handle
+        .delete_backlog_story(second)
+        .unwrap();
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert!(db_state
+        .backlog()
+        .is_empty());
+    assert!(!db_state
+        .stories
+        .contains_key(&second));
+}
+
+#[test]
+fn attach_story_should_only_move_backlog_stories() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+    let backlog_id = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+
+    assert!(matches!(
+        jira_handle.attach_story(story_id, epic_id),
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        jira_handle.delete_backlog_story(story_id),
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        jira_handle.attach_story(999, epic_id),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+    assert!(matches!(
+        jira_handle.attach_story(backlog_id, 999),
+        Err(Error::NotFound {
+            kind: ItemKind::Epic,
+            id: 999
+        })
+    ));
+    assert_eq!(
+        jira_handle
+            .read_full_record()
+            .unwrap()
+            .backlog(),
+        vec![backlog_id]
+    );
+}
+
+#[test]
+fn sprints_should_be_planned_started_and_completed() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let done_id = jira_handle
+        .create_story(Story::new("done".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+    let open_id = jira_handle
+        .create_story(Story::new("open".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+    let sprint_id = jira_handle
+        .create_sprint(Sprint::new(
+            "sprint 1".to_owned(),
+            "".to_owned(),
+            None,
+            None,
+        ))
+        .unwrap();
+    let next_id = jira_handle
+        .create_sprint(Sprint::new(
+            "sprint 2".to_owned(),
+            "".to_owned(),
+            None,
+            None,
+        ))
+        .unwrap();
+
+    jira_handle
+        .add_story_to_sprint(sprint_id, done_id)
+        .unwrap();
+    jira_handle
+        .add_story_to_sprint(sprint_id, open_id)
+        .unwrap();
+    assert!(matches!(
+        jira_handle.add_story_to_sprint(next_id, open_id),
+        Err(Error::Conflict(_))
+    ));
+
+    jira_handle
+        .start_sprint(sprint_id)
+        .unwrap();
+    assert!(matches!(
+        jira_handle.start_sprint(next_id),
+        Err(Error::Conflict(_))
+    ));
+    assert_eq!(
+        jira_handle
+            .read_full_record()
+            .unwrap()
+            .active_sprint(),
+        Some(sprint_id)
+    );
+
+    jira_handle
+        .update_story_status(done_id, Status::Resolved)
+        .unwrap();
+    assert_eq!(
+        jira_handle
+            .complete_sprint(sprint_id)
+            .unwrap(),
+        vec![open_id]
+    );
+    assert!(matches!(
+        jira_handle.add_story_to_sprint(sprint_id, open_id),
+        Err(Error::Conflict(_))
+    ));
+
+    jira_handle
+        .roll_over_stories(sprint_id, next_id)
+        .unwrap();
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.sprints[&sprint_id].state, SprintState::Completed);
+    assert_eq!(db_state.sprints[&sprint_id].stories, vec![done_id, open_id]);
+    assert_eq!(db_state.sprints[&next_id].stories, vec![open_id]);
+    assert_eq!(db_state.active_sprint(), None);
+    assert_eq!(db_state.next_planned_sprint(), Some(next_id));
+}
+
+#[test]
+fn create_sprint_should_validate_input() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+
+    assert!(matches!(
+        jira_handle.create_sprint(Sprint::new(" ".to_owned(), "".to_owned(), None, None)),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        jira_handle.create_sprint(Sprint::new(
+            "sprint".to_owned(),
+            "".to_owned(),
+            NaiveDate::from_ymd_opt(2024, 2, 1),
+            NaiveDate::from_ymd_opt(2024, 1, 1),
+        )),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        jira_handle.start_sprint(999),
+        Err(Error::NotFound {
+            kind: ItemKind::Sprint,
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn delete_story_should_remove_it_from_sprints() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let story_id = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    let sprint_id = jira_handle
+        .create_sprint(Sprint::new("sprint".to_owned(), "".to_owned(), None, None))
+        .unwrap();
+    jira_handle
+        .add_story_to_sprint(sprint_id, story_id)
+        .unwrap();
+
+    jira_handle
+        .delete_backlog_story(story_id)
+        .unwrap();
+
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .sprints[&sprint_id]
+        .stories
+        .is_empty());
+}
+
+#[test]
+fn update_story_status_should_record_history() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let story_id = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+
+    for status in [Status::InProgress, Status::InProgress, Status::Resolved] {
+        jira_handle
+            .update_story_status(story_id, status)
+            .unwrap();
+    }
+    jira_handle
+        .update_story_points(story_id, Some(5))
+        .unwrap();
+
+    let story = &jira_handle
+        .read_full_record()
+        .unwrap()
+        .stories[&story_id];
+    let statuses: Vec<Status> = story
+        .history
+        .iter()
+        .map(|change| change.status)
+        .collect();
+    assert_eq!(statuses, [Status::InProgress, Status::Resolved]);
+    assert_eq!(story.points, Some(5));
+    assert!(matches!(
+        jira_handle.update_story_points(999, None),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn update_due_should_set_and_clear_dates() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+    let due = NaiveDate::from_ymd_opt(2026, 11, 1);
+
+    jira_handle
+        .update_epic_due(epic_id, due)
+        .unwrap();
+    jira_handle
+        .update_story_due(story_id, due)
+        .unwrap();
+    jira_handle
+        .update_epic_due(epic_id, None)
+        .unwrap();
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.epics[&epic_id].due, None);
+    assert_eq!(db_state.stories[&story_id].due, due);
+    assert!(matches!(
+        jira_handle.update_epic_due(999, due),
+        Err(Error::NotFound {
+            kind: ItemKind::Epic,
+            id: 999
+        })
+    ));
+    assert!(matches!(
+        jira_handle.update_story_due(999, due),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn update_field_should_set_and_remove_values() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+
+    jira_handle
+        .update_epic_field(epic_id, "customer", Some("acme".to_owned()))
+        .unwrap();
+    jira_handle
+        .update_story_field(story_id, "environment", Some("staging".to_owned()))
+        .unwrap();
+    jira_handle
+        .update_story_field(story_id, "environment", None)
+        .unwrap();
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.epics[&epic_id].fields["customer"], "acme");
+    assert!(db_state.stories[&story_id]
+        .fields
+        .is_empty());
+    assert!(matches!(
+        jira_handle.update_story_field(999, "customer", None),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn timers_should_log_work_on_stop() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let first = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    let second = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+
+    assert!(matches!(
+        jira_handle.stop_timer("alice", "".to_owned()),
+        Err(Error::Conflict(_))
+    ));
+    jira_handle
+        .start_timer(first, "alice")
+        .unwrap();
+    assert!(matches!(
+        jira_handle.start_timer(first, "alice"),
+        Err(Error::Conflict(_))
+    ));
+
+    // pretend the timer has been running for a minute and a half
+    let mut db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    let timer = db_state
+        .timers
+        .get_mut("alice")
+        .unwrap();
+    timer.started_at -= chrono::Duration::seconds(90);
+    jira_handle
+        .database
+        .write_db(&db_state)
+        .unwrap();
+
+    // switching stories logs the running timer
+    jira_handle
+        .start_timer(second, "alice")
+        .unwrap();
+    let worklog = jira_handle
+        .stop_timer("alice", "review".to_owned())
+        .unwrap();
+    assert_eq!(worklog.user, "alice");
+    assert_eq!(worklog.note, "review");
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert!(db_state
+        .timers
+        .is_empty());
+    assert_eq!(db_state.stories[&first].worklogs[0].minutes, 2);
+    assert_eq!(db_state.stories[&second].worklogs, [worklog]);
+    assert!(matches!(
+        jira_handle.start_timer(999, "alice"),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn delete_story_should_drop_its_timers() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let story_id = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    jira_handle
+        .start_timer(story_id, "alice")
+        .unwrap();
+
+    jira_handle
+        .delete_backlog_story(story_id)
+        .unwrap();
+
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .timers
+        .is_empty());
+}
+
+#[test]
+fn link_stories_should_reject_cycles_and_duplicates() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let ids: Vec<u32> = (0..3)
+        .map(|_| {
+            jira_handle
+                .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+                .unwrap()
+        })
+        .collect();
+    let (a, b, c) = (ids[0], ids[1], ids[2]);
+
+    jira_handle
+        .link_stories(a, LinkKind::Blocks, b)
+        .unwrap();
+    // stored as b blocks c
+    jira_handle
+        .link_stories(c, LinkKind::IsBlockedBy, b)
+        .unwrap();
+    jira_handle
+        .link_stories(a, LinkKind::RelatesTo, c)
+        .unwrap();
+
+    match jira_handle.link_stories(c, LinkKind::Blocks, a) {
+        Err(Error::Conflict(message)) => {
+            assert!(message.ends_with(&format!("{a} -> {b} -> {c} -> {a}")))
+        }
+        other => panic!("expected a cycle conflict, got {other:?}"),
+    }
+    assert!(matches!(
+        jira_handle.link_stories(c, LinkKind::RelatesTo, a),
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        jira_handle.link_stories(a, LinkKind::Duplicates, a),
+        Err(Error::Validation(_))
+    ));
+    assert!(matches!(
+        jira_handle.link_stories(a, LinkKind::Blocks, 999),
+        Err(Error::NotFound {
+            kind: ItemKind::Story,
+            id: 999
+        })
+    ));
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(
+        db_state.links[1],
+        Link {
+            from: b,
+            to: c,
+            kind: LinkKind::Blocks
+        }
+    );
+    assert_eq!(db_state.open_blockers(c), [b]);
+
+    jira_handle
+        .update_story_status(b, Status::Resolved)
+        .unwrap();
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .open_blockers(c)
+        .is_empty());
+}
+
+#[test]
+fn unlink_stories_should_remove_links_in_both_directions() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    let a = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    let b = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    jira_handle
+        .link_stories(a, LinkKind::Blocks, b)
+        .unwrap();
+
+    jira_handle
+        .unlink_stories(b, a)
+        .unwrap();
+
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .links
+        .is_empty());
+    assert!(matches!(
+        jira_handle.unlink_stories(a, b),
+        Err(Error::Conflict(_))
+    ));
+
+    jira_handle
+        .link_stories(a, LinkKind::Duplicates, b)
+        .unwrap();
+    jira_handle
+        .delete_backlog_story(b)
+        .unwrap();
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .links
+        .is_empty());
+}
+
+#[test]
+fn create_project_should_validate_keys_and_names() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+
+    jira_handle
+        .create_project("API", "backend".to_owned())
+        .unwrap();
+
+    assert!(matches!(
+        jira_handle.create_project("API", "again".to_owned()),
+        Err(Error::Conflict(_))
+    ));
+    for key in ["A", "api", "2FA", "API-1", "ABCDEFGHIJK"] {
+        assert!(matches!(
+            jira_handle.create_project(key, "name".to_owned()),
+            Err(Error::Validation(_))
+        ));
+    }
+    assert!(matches!(
+        jira_handle.create_project("WEB", " ".to_owned()),
+        Err(Error::Validation(_))
+    ));
+    assert_eq!(
+        jira_handle
+            .read_full_record()
+            .unwrap()
+            .projects
+            .into_keys()
+            .collect::<Vec<_>>(),
+        ["API"]
+    );
+}
+
+#[test]
+fn project_items_should_be_keyed_per_project() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    jira_handle
+        .create_project("API", "backend".to_owned())
+        .unwrap();
+    jira_handle
+        .create_project("WEB", "frontend".to_owned())
+        .unwrap();
+
+    let api_epic = jira_handle
+        .create_project_epic("API", Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let web_epic = jira_handle
+        .create_project_epic("WEB", Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let api_story = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), api_epic)
+        .unwrap();
+    let web_story = jira_handle
+        .create_project_backlog_story("WEB", Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    let loose_story = jira_handle
+        .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+    jira_handle
+        .attach_story(loose_story, api_epic)
+        .unwrap();
+
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    let key = |key: &Option<ItemKey>| {
+        key.as_ref()
+            .map(ToString::to_string)
+    };
+    assert_eq!(
+        key(&db_state.epics[&api_epic].key).as_deref(),
+        Some("API-1")
+    );
+    assert_eq!(
+        key(&db_state.epics[&web_epic].key).as_deref(),
+        Some("WEB-1")
+    );
+    assert_eq!(
+        key(&db_state.stories[&api_story].key).as_deref(),
+        Some("API-2")
+    );
+    assert_eq!(
+        key(&db_state.stories[&web_story].key).as_deref(),
+        Some("WEB-2")
+    );
+    assert_eq!(
+        key(&db_state.stories[&loose_story].key).as_deref(),
+        Some("API-3")
+    );
+    assert_eq!(db_state.projects["API"].last_number, 3);
+
+    // items outside of projects keep working without keys
+    let epic_id = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let story_id = jira_handle
+        .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+        .unwrap();
+    assert!(jira_handle
+        .read_full_record()
+        .unwrap()
+        .stories[&story_id]
+        .key
+        .is_none());
+    assert!(matches!(
+        jira_handle.create_project_epic("OPS", Epic::new("epic".to_owned(), "".to_owned())),
+        Err(Error::ProjectNotFound(project)) if project == "OPS"
+    ));
+}
+
+#[test]
+fn attach_story_should_keep_stories_in_their_project() {
+    let jira_handle = JiraHandle {
+        database: Box::new(MockDB::new()),
+    };
+    jira_handle
+        .create_project("API", "backend".to_owned())
+        .unwrap();
+    jira_handle
+        .create_project("WEB", "frontend".to_owned())
+        .unwrap();
+    let api_epic = jira_handle
+        .create_project_epic("API", Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let loose_epic = jira_handle
+        .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    let web_story = jira_handle
+        .create_project_backlog_story("WEB", Story::new("story".to_owned(), "".to_owned()))
+        .unwrap();
+
+    assert!(matches!(
+        jira_handle.attach_story(web_story, api_epic),
+        Err(Error::Conflict(_))
+    ));
+    assert!(matches!(
+        jira_handle.attach_story(web_story, loose_epic),
+        Err(Error::Conflict(_))
+    ));
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.backlog(), vec![web_story]);
+    assert_eq!(db_state.projects["API"].last_number, 1);
+
+    let web_epic = jira_handle
+        .create_project_epic("WEB", Epic::new("epic".to_owned(), "".to_owned()))
+        .unwrap();
+    jira_handle
+        .attach_story(web_story, web_epic)
+        .unwrap();
+    let db_state = jira_handle
+        .read_full_record()
+        .unwrap();
+    assert_eq!(db_state.epics[&web_epic].stories, vec![web_story]);
+    assert_eq!(
+        db_state.stories[&web_story]
+            .key
+            .as_ref()
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("WEB-1")
+    );
+}
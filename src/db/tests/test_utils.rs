@@ -1,6 +1,8 @@
-use super::{DBState, Database};
-use anyhow::{Ok, Result};
-use std::{cell::RefCell, collections::HashMap};
+use super::{DBState, Database, Result};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+};
 
 pub struct MockDB {
     pub state: RefCell<DBState>,
@@ -13,11 +15,22 @@ impl MockDB {
                 last_item_id: 0,
                 epics: HashMap::new(),
                 stories: HashMap::new(),
+                subtasks: HashMap::new(),
+                sprints: HashMap::new(),
+                timers: HashMap::new(),
+                links: vec![],
+                projects: BTreeMap::new(),
             }),
         }
     }
 }
 
+impl Default for MockDB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Database for MockDB {
     fn read_db(&self) -> Result<DBState> {
         let st = self
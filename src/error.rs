@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// The kind of item an [`Error::NotFound`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Epic,
+    Story,
+    SubTask,
+    Sprint,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Epic => write!(f, "epic"),
+            Self::Story => write!(f, "story"),
+            Self::SubTask => write!(f, "sub-task"),
+            Self::Sprint => write!(f, "sprint"),
+        }
+    }
+}
+
+/// Errors returned by [`Database`](crate::db::Database) implementations and
+/// [`JiraHandle`](crate::db::JiraHandle).
+#[derive(Debug, Error)]
+pub enum Error {
+    /// No item of `kind` with `id` exists on the board.
+    #[error("could not find {kind} with id {id}")]
+    NotFound { kind: ItemKind, id: u32 },
+    /// No project with this key exists on the board.
+    #[error("could not find project {0}")]
+    ProjectNotFound(String),
+    /// The database file could not be read or written.
+    #[error("could not access the database file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The database file is not a valid board.
+    #[error("the database file is corrupt: {0}")]
+    Corrupt(#[from] serde_json::Error),
+    /// The request does not match the current state of the board, e.g. a
+    /// story that is not part of the given epic.
+    #[error("{0}")]
+    Conflict(String),
+    /// The request itself is invalid, e.g. an empty name.
+    #[error("{0}")]
+    Validation(String),
+}
+
+impl Error {
+    pub fn epic_not_found(id: u32) -> Self {
+        Error::NotFound {
+            kind: ItemKind::Epic,
+            id,
+        }
+    }
+
+    pub fn story_not_found(id: u32) -> Self {
+        Error::NotFound {
+            kind: ItemKind::Story,
+            id,
+        }
+    }
+
+    pub fn subtask_not_found(id: u32) -> Self {
+        Error::NotFound {
+            kind: ItemKind::SubTask,
+            id,
+        }
+    }
+
+    pub fn sprint_not_found(id: u32) -> Self {
+        Error::NotFound {
+            kind: ItemKind::Sprint,
+            id,
+        }
+    }
+
+    /// Process exit code used by the binary when a command fails with this
+    /// error; 1 is left for failures that are not an [`Error`](enum@Error).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::NotFound { .. } | Error::ProjectNotFound(_) => 2,
+            Error::Io(_) => 3,
+            Error::Corrupt(_) => 4,
+            Error::Conflict(_) => 5,
+            Error::Validation(_) => 6,
+        }
+    }
+}
+
+/// Shorthand for results carrying the library [`Error`](enum@Error).
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,312 @@
+use std::fmt::Write;
+use std::fs;
+
+use anyhow::Result;
+
+use crate::db::JiraHandle;
+use crate::error::Error;
+use crate::models::{DBState, LinkKind, Status};
+
+/// Diagram languages the graph can be exported as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+pub fn export_graph(
+    db: &JiraHandle,
+    format: GraphFormat,
+    epic_id: Option<u32>,
+    file_path: &str,
+) -> Result<()> {
+    let db_state = db.read_full_record()?;
+    let graph = match format {
+        GraphFormat::Dot => render_dot(&db_state, epic_id)?,
+        GraphFormat::Mermaid => render_mermaid(&db_state, epic_id)?,
+    };
+    fs::write(file_path, graph)?;
+    Ok(())
+}
+
+// same palette as the html dashboard
+fn status_color(status: Status) -> &'static str {
+    match status {
+        Status::Open => "#54aeff",
+        Status::InProgress => "#d4a72c",
+        Status::Resolved => "#4ac26b",
+        Status::Closed => "#8c959f",
+    }
+}
+
+fn status_class(status: Status) -> &'static str {
+    match status {
+        Status::Open => "open",
+        Status::InProgress => "in_progress",
+        Status::Resolved => "resolved",
+        Status::Closed => "closed",
+    }
+}
+
+struct Node {
+    id: String,
+    label: String,
+    status: Status,
+}
+
+struct Edge {
+    from: String,
+    to: String,
+    // None for the epic to story hierarchy
+    kind: Option<LinkKind>,
+}
+
+// the nodes and edges of the whole board, or of one epic and its stories;
+// links are only drawn between stories that are part of the graph
+fn graph(db_state: &DBState, epic_id: Option<u32>) -> Result<(Vec<Node>, Vec<Edge>), Error> {
+    let mut epic_ids: Vec<u32> = match epic_id {
+        Some(epic_id)
+            if !db_state
+                .epics
+                .contains_key(&epic_id) =>
+        {
+            return Err(Error::epic_not_found(epic_id))
+        }
+        Some(epic_id) => vec![epic_id],
+        None => db_state
+            .epics
+            .keys()
+            .copied()
+            .collect(),
+    };
+    epic_ids.sort_unstable();
+
+    let mut nodes = vec![];
+    let mut edges = vec![];
+    let mut story_ids = vec![];
+    for id in epic_ids {
+        let epic = &db_state.epics[&id];
+        nodes.push(Node {
+            id: format!("epic_{}", id),
+            label: format!("Epic {}: {}", id, epic.name),
+            status: epic.status,
+        });
+        for story_id in epic
+            .stories
+            .iter()
+        {
+            edges.push(Edge {
+                from: format!("epic_{}", id),
+                to: format!("story_{}", story_id),
+                kind: None,
+            });
+            story_ids.push(*story_id);
+        }
+    }
+    if epic_id.is_none() {
+        story_ids.extend(db_state.backlog());
+    }
+    story_ids.retain(|id| {
+        db_state
+            .stories
+            .contains_key(id)
+    });
+
+    for id in story_ids.iter() {
+        let story = &db_state.stories[id];
+        nodes.push(Node {
+            id: format!("story_{}", id),
+            label: format!("Story {}: {}", id, story.name),
+            status: story.status,
+        });
+    }
+    for link in db_state
+        .links
+        .iter()
+        .filter(|link| story_ids.contains(&link.from) && story_ids.contains(&link.to))
+    {
+        edges.push(Edge {
+            from: format!("story_{}", link.from),
+            to: format!("story_{}", link.to),
+            kind: Some(link.kind),
+        });
+    }
+    Ok((nodes, edges))
+}
+
+/// Graphviz digraph of epics, their stories and the links between stories,
+/// filled by status.
+pub fn render_dot(db_state: &DBState, epic_id: Option<u32>) -> Result<String, Error> {
+    let (nodes, edges) = graph(db_state, epic_id)?;
+    let mut dot = String::new();
+    dot.push_str("digraph board {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+    for node in nodes {
+        let _ = writeln!(
+            dot,
+            "    {} [label=\"{}\\n{}\", fillcolor=\"{}\"];",
+            node.id,
+            escape_dot(&node.label),
+            node.status,
+            status_color(node.status)
+        );
+    }
+    for edge in edges {
+        let attributes = match edge.kind {
+            None => String::new(),
+            Some(LinkKind::RelatesTo) => {
+                " [label=\"relates to\", style=dotted, dir=none]".to_owned()
+            }
+            Some(kind) => format!(" [label=\"{}\", style=dashed]", kind),
+        };
+        let _ = writeln!(dot, "    {} -> {}{};", edge.from, edge.to, attributes);
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+/// Mermaid flowchart of epics, their stories and the links between
+/// stories, with one class per status.
+pub fn render_mermaid(db_state: &DBState, epic_id: Option<u32>) -> Result<String, Error> {
+    let (nodes, edges) = graph(db_state, epic_id)?;
+    let mut mermaid = String::new();
+    mermaid.push_str("flowchart LR\n");
+    for node in nodes.iter() {
+        let _ = writeln!(
+            mermaid,
+            "    {}[\"{}<br/>{}\"]",
+            node.id,
+            escape_mermaid(&node.label),
+            node.status
+        );
+    }
+    for edge in edges {
+        let arrow = match edge.kind {
+            None => "-->".to_owned(),
+            Some(LinkKind::RelatesTo) => "-.-|relates to|".to_owned(),
+            Some(kind) => format!("-.->|{}|", kind),
+        };
+        let _ = writeln!(mermaid, "    {} {} {}", edge.from, arrow, edge.to);
+    }
+    for status in Status::ALL {
+        let ids: Vec<&str> = nodes
+            .iter()
+            .filter(|node| node.status == status)
+            .map(|node| {
+                node.id
+                    .as_str()
+            })
+            .collect();
+        if ids.is_empty() {
+            continue;
+        }
+        let _ = writeln!(
+            mermaid,
+            "    classDef {} fill:{}",
+            status_class(status),
+            status_color(status)
+        );
+        let _ = writeln!(
+            mermaid,
+            "    class {} {}",
+            ids.join(","),
+            status_class(status)
+        );
+    }
+    Ok(mermaid)
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', " ")
+}
+
+// mermaid labels take html entities written as #name;
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+        .replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Epic, Link, Story};
+
+    fn board() -> DBState {
+        let mut db_state = DBState {
+            links: vec![
+                Link {
+                    from: 2,
+                    to: 3,
+                    kind: LinkKind::Blocks,
+                },
+                Link {
+                    from: 3,
+                    to: 5,
+                    kind: LinkKind::RelatesTo,
+                },
+            ],
+            ..DBState::default()
+        };
+        let mut epic = Epic::new("Release \"1\"".to_owned(), "".to_owned());
+        epic.stories = vec![2, 3];
+        db_state
+            .epics
+            .insert(1, epic);
+        db_state
+            .epics
+            .insert(4, Epic::new("Later".to_owned(), "".to_owned()));
+        let mut story = Story::new("Migrate".to_owned(), "".to_owned());
+        story.status = Status::InProgress;
+        db_state
+            .stories
+            .insert(2, story);
+        db_state
+            .stories
+            .insert(3, Story::new("Deploy".to_owned(), "".to_owned()));
+        db_state
+            .stories
+            .insert(5, Story::new("Docs".to_owned(), "".to_owned()));
+        db_state
+    }
+
+    #[test]
+    fn render_dot_should_draw_hierarchy_and_links() {
+        let dot = render_dot(&board(), None).unwrap();
+
+        assert!(dot.starts_with("digraph board {\n"));
+        assert!(dot.contains(
+            "    epic_1 [label=\"Epic 1: Release \\\"1\\\"\\nOPEN\", fillcolor=\"#54aeff\"];\n"
+        ));
+        assert!(dot.contains("fillcolor=\"#d4a72c\""));
+        assert!(dot.contains("    epic_1 -> story_2;\n"));
+        assert!(dot.contains("    story_2 -> story_3 [label=\"blocks\", style=dashed];\n"));
+        assert!(dot
+            .contains("    story_3 -> story_5 [label=\"relates to\", style=dotted, dir=none];\n"));
+        assert!(dot.contains("epic_4 ["));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn render_mermaid_should_filter_to_one_epic() {
+        let mermaid = render_mermaid(&board(), Some(1)).unwrap();
+
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    epic_1[\"Epic 1: Release #quot;1#quot;<br/>OPEN\"]\n"));
+        assert!(mermaid.contains("    story_2 -.->|blocks| story_3\n"));
+        assert!(mermaid.contains("    class epic_1,story_3 open\n"));
+        assert!(mermaid.contains("    class story_2 in_progress\n"));
+        // the backlog story and the other epic are left out, and so is the
+        // link to the backlog story
+        assert!(!mermaid.contains("story_5"));
+        assert!(!mermaid.contains("epic_4"));
+        assert!(matches!(
+            render_mermaid(&board(), Some(999)),
+            Err(Error::NotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt::Write;
+use std::fs;
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::db::JiraHandle;
+use crate::models::{DBState, Epic, Status};
+
+use super::escape;
+
+// inline css and js keep the exported file self-contained so it can be
+// opened from disk or dropped on any static host
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2328; }
+h1, h2 { font-weight: 600; }
+.summary { display: flex; gap: 1rem; margin-bottom: 2rem; }
+.summary div { padding: 0.75rem 1rem; border-radius: 6px; background: #f6f8fa; }
+.epic { margin-bottom: 1.5rem; }
+.bar { height: 12px; width: 100%; max-width: 480px; background: #eaeef2; border-radius: 6px; overflow: hidden; display: flex; }
+.bar span { display: block; height: 100%; }
+.status-open { background: #54aeff; }
+.status-in-progress { background: #d4a72c; }
+.status-resolved { background: #4ac26b; }
+.status-closed { background: #8c959f; }
+.breakdown { font-size: 0.85rem; color: #57606a; }
+table { border-collapse: collapse; width: 100%; margin-top: 1rem; }
+th, td { text-align: left; padding: 0.4rem 0.6rem; border-bottom: 1px solid #d0d7de; }
+.filters { display: flex; gap: 0.5rem; }
+"#;
+
+const SCRIPT: &str = r##"
+function filterStories() {
+    var text = document.getElementById("filter-text").value.toLowerCase();
+    var status = document.getElementById("filter-status").value;
+    var rows = document.querySelectorAll("#stories tbody tr");
+    rows.forEach(function (row) {
+        var matchesText = row.textContent.toLowerCase().indexOf(text) !== -1;
+        var matchesStatus = status === "" || row.dataset.status === status;
+        row.style.display = matchesText && matchesStatus ? "" : "none";
+    });
+}
+"##;
+
+pub fn export_html(db: &JiraHandle, file_path: &str) -> Result<()> {
+    let db_state = db.read_full_record()?;
+    fs::write(file_path, render_html(&db_state))?;
+    Ok(())
+}
+
+pub fn render_html(db_state: &DBState) -> String {
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Jira board</title>\n");
+    let _ = writeln!(html, "<style>{}</style>", STYLE);
+    html.push_str("</head>\n<body>\n<h1>Jira board</h1>\n");
+
+    let all_statuses = db_state
+        .stories
+        .values()
+        .map(|story| &story.status);
+    html.push_str("<div class=\"summary\">\n");
+    for (status, count) in count_by_status(all_statuses) {
+        let _ = writeln!(html, "<div>{}: <strong>{}</strong></div>", status, count);
+    }
+    html.push_str("</div>\n");
+
+    html.push_str("<h2>Epics</h2>\n");
+    for id in db_state
+        .epics
+        .keys()
+        .sorted()
+    {
+        let epic = &db_state.epics[id];
+        html.push_str(&render_epic(*id, epic, db_state));
+    }
+
+    html.push_str(&render_story_table(db_state));
+
+    let _ = writeln!(html, "<script>{}</script>", SCRIPT);
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+fn render_epic(epic_id: u32, epic: &Epic, db_state: &DBState) -> String {
+    let statuses = epic
+        .stories
+        .iter()
+        .filter_map(|id| {
+            db_state
+                .stories
+                .get(id)
+        })
+        .map(|story| &story.status);
+    let counts = count_by_status(statuses);
+    let total: usize = counts
+        .iter()
+        .map(|(_, count)| count)
+        .sum();
+    let done: usize = counts
+        .iter()
+        .filter(|(status, _)| status.is_done())
+        .map(|(_, count)| count)
+        .sum();
+
+    let mut html = String::new();
+    html.push_str("<div class=\"epic\">\n");
+    let _ = writeln!(
+        html,
+        "<h3>#{} {} <small>({})</small></h3>",
+        epic_id,
+        escape(&epic.name),
+        epic.status
+    );
+    let _ = writeln!(html, "<p>{}</p>", escape(&epic.description));
+
+    html.push_str("<div class=\"bar\">");
+    for (status, count) in &counts {
+        if *count == 0 {
+            continue;
+        }
+        let _ = write!(
+            html,
+            "<span class=\"{}\" style=\"width: {:.1}%\"></span>",
+            status_class(status),
+            *count as f64 * 100.0 / total as f64
+        );
+    }
+    html.push_str("</div>\n");
+
+    let breakdown = counts
+        .iter()
+        .map(|(status, count)| format!("{}: {}", status, count))
+        .join(" | ");
+    let _ = writeln!(
+        html,
+        "<div class=\"breakdown\">{}/{} done &middot; {}</div>",
+        done, total, breakdown
+    );
+    html.push_str("</div>\n");
+
+    html
+}
+
+fn render_story_table(db_state: &DBState) -> String {
+    let mut html = String::new();
+
+    html.push_str("<h2>Stories</h2>\n<div class=\"filters\">\n");
+    html.push_str(
+        "<input id=\"filter-text\" placeholder=\"Filter stories\" oninput=\"filterStories()\">\n",
+    );
+    html.push_str("<select id=\"filter-status\" onchange=\"filterStories()\">\n<option value=\"\">All statuses</option>\n");
+    for status in Status::ALL {
+        let _ = writeln!(
+            html,
+            "<option value=\"{}\">{}</option>",
+            status_class(&status),
+            status
+        );
+    }
+    html.push_str("</select>\n</div>\n");
+
+    // one column per custom field used by any story, so the text filter
+    // also finds field values
+    let field_names: Vec<&String> = db_state
+        .stories
+        .values()
+        .flat_map(|story| {
+            story
+                .fields
+                .keys()
+        })
+        .unique()
+        .sorted()
+        .collect();
+    html.push_str("<table id=\"stories\">\n<thead><tr><th>id</th><th>epic</th><th>name</th><th>description</th><th>status</th>");
+    for name in field_names.iter() {
+        let _ = write!(html, "<th>{}</th>", escape(name));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+    for epic_id in db_state
+        .epics
+        .keys()
+        .sorted()
+    {
+        let epic = &db_state.epics[epic_id];
+        for story_id in epic
+            .stories
+            .iter()
+            .sorted()
+        {
+            if let Some(story) = db_state
+                .stories
+                .get(story_id)
+            {
+                let _ = write!(
+                    html,
+                    "<tr data-status=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>",
+                    status_class(&story.status),
+                    story_id,
+                    escape(&epic.name),
+                    escape(&story.name),
+                    escape(&story.description),
+                    story.status
+                );
+                for name in field_names.iter() {
+                    let value = story
+                        .fields
+                        .get(*name)
+                        .map_or("", String::as_str);
+                    let _ = write!(html, "<td>{}</td>", escape(value));
+                }
+                html.push_str("</tr>\n");
+            }
+        }
+    }
+    html.push_str("</tbody>\n</table>\n");
+
+    html
+}
+
+fn count_by_status<'a>(statuses: impl Iterator<Item = &'a Status>) -> Vec<(Status, usize)> {
+    let statuses: Vec<&Status> = statuses.collect();
+    Status::ALL
+        .into_iter()
+        .map(|status| {
+            let count = statuses
+                .iter()
+                .filter(|s| ***s == status)
+                .count();
+            (status, count)
+        })
+        .collect()
+}
+
+fn status_class(status: &Status) -> &'static str {
+    match status {
+        Status::Open => "status-open",
+        Status::InProgress => "status-in-progress",
+        Status::Resolved => "status-resolved",
+        Status::Closed => "status-closed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use crate::models::Story;
+
+    fn setup_db() -> JiraHandle {
+        let db = JiraHandle {
+            database: Box::new(MockDB::new()),
+        };
+        let epic_id = db
+            .create_epic(Epic::new("Release <1.0>".to_owned(), "ship it".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("done".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_status(story_id, Status::Resolved)
+            .unwrap();
+        db.create_story(Story::new("todo".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn render_html_should_include_epic_progress_and_stories() {
+        let db = setup_db();
+        let html = render_html(
+            &db.read_full_record()
+                .unwrap(),
+        );
+
+        assert!(html.contains("1/2 done"));
+        assert!(html.contains("<span class=\"status-resolved\" style=\"width: 50.0%\"></span>"));
+        assert!(html.contains("<tr data-status=\"status-open\"><td>3</td>"));
+        assert!(html.contains("id=\"filter-status\""));
+    }
+
+    #[test]
+    fn render_html_should_add_custom_field_columns() {
+        let db = setup_db();
+        db.update_story_field(2, "customer", Some("Acme & Co".to_owned()))
+            .unwrap();
+        let html = render_html(
+            &db.read_full_record()
+                .unwrap(),
+        );
+
+        assert!(html.contains("<th>status</th><th>customer</th></tr>"));
+        assert!(html.contains("<td>RESOLVED</td><td>Acme &amp; Co</td></tr>"));
+        assert!(html.contains("<td>OPEN</td><td></td></tr>"));
+    }
+
+    #[test]
+    fn render_html_should_escape_user_text() {
+        let db = setup_db();
+        let html = render_html(
+            &db.read_full_record()
+                .unwrap(),
+        );
+
+        assert!(html.contains("Release &lt;1.0&gt;"));
+        assert!(!html.contains("Release <1.0>"));
+    }
+
+    #[test]
+    fn render_html_should_not_reference_external_assets() {
+        let db = setup_db();
+        let html = render_html(
+            &db.read_full_record()
+                .unwrap(),
+        );
+
+        assert!(!html.contains("src=\"http"));
+        assert!(!html.contains("href=\"http"));
+    }
+}
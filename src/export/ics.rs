@@ -0,0 +1,254 @@
+use std::fs;
+
+use anyhow::Result;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+use crate::db::JiraHandle;
+use crate::models::DBState;
+
+// domain part of the event uids; uids only depend on the item id and the
+// kind of date so calendar clients update events on re-import
+const UID_DOMAIN: &str = "rust-jira-cli";
+// content lines longer than this many bytes are folded (RFC 5545, 3.1)
+const LINE_LIMIT: usize = 75;
+
+pub fn export_ics(db: &JiraHandle, file_path: &str) -> Result<()> {
+    let db_state = db.read_full_record()?;
+    fs::write(file_path, render_ics(&db_state, Utc::now()))?;
+    Ok(())
+}
+
+// an all-day event on one date
+struct Event {
+    uid: String,
+    date: NaiveDate,
+    summary: String,
+    description: String,
+}
+
+/// Calendar with an all-day event for every epic target date, story due
+/// date and sprint start and end, stamped with `stamp`.
+pub fn render_ics(db_state: &DBState, stamp: DateTime<Utc>) -> String {
+    let mut events = vec![];
+
+    let mut epic_ids: Vec<&u32> = db_state
+        .epics
+        .keys()
+        .collect();
+    epic_ids.sort_unstable();
+    for id in epic_ids {
+        let epic = &db_state.epics[id];
+        if let Some(due) = epic.due {
+            events.push(Event {
+                uid: format!("epic-{}-due@{}", id, UID_DOMAIN),
+                date: due,
+                summary: format!("Epic {}: {} (target)", id, epic.name),
+                description: format!("status: {}", epic.status),
+            });
+        }
+    }
+
+    let mut story_ids: Vec<&u32> = db_state
+        .stories
+        .keys()
+        .collect();
+    story_ids.sort_unstable();
+    for id in story_ids {
+        let story = &db_state.stories[id];
+        if let Some(due) = story.due {
+            events.push(Event {
+                uid: format!("story-{}-due@{}", id, UID_DOMAIN),
+                date: due,
+                summary: format!("Story {}: {} (due)", id, story.name),
+                description: format!("status: {}", story.status),
+            });
+        }
+    }
+
+    let mut sprint_ids: Vec<&u32> = db_state
+        .sprints
+        .keys()
+        .collect();
+    sprint_ids.sort_unstable();
+    for id in sprint_ids {
+        let sprint = &db_state.sprints[id];
+        for (kind, date) in [("start", sprint.start), ("end", sprint.end)] {
+            let Some(date) = date else {
+                continue;
+            };
+            events.push(Event {
+                uid: format!("sprint-{}-{}@{}", id, kind, UID_DOMAIN),
+                date,
+                summary: format!("Sprint {}: {} ({})", id, sprint.name, kind),
+                description: sprint
+                    .goal
+                    .clone(),
+            });
+        }
+    }
+
+    let mut ics = String::new();
+    let mut line = |text: String| {
+        ics.push_str(&fold(&text));
+        ics.push_str("\r\n");
+    };
+    line("BEGIN:VCALENDAR".to_owned());
+    line("VERSION:2.0".to_owned());
+    line(format!("PRODID:-//{}//board//EN", UID_DOMAIN));
+    line("CALSCALE:GREGORIAN".to_owned());
+    for event in events {
+        line("BEGIN:VEVENT".to_owned());
+        line(format!("UID:{}", event.uid));
+        line(format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")));
+        line(format!(
+            "DTSTART;VALUE=DATE:{}",
+            event
+                .date
+                .format("%Y%m%d")
+        ));
+        line(format!(
+            "DTEND;VALUE=DATE:{}",
+            (event.date + Days::new(1)).format("%Y%m%d")
+        ));
+        line(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if !event
+            .description
+            .is_empty()
+        {
+            line(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        }
+        line("END:VEVENT".to_owned());
+    }
+    line("END:VCALENDAR".to_owned());
+    ics
+}
+
+// TEXT values escape backslashes, separators and line breaks
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// splits a content line into chunks of at most LINE_LIMIT bytes without
+// breaking characters; continuation lines start with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_LIMIT {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Epic, Sprint, Story};
+
+    fn board() -> DBState {
+        let mut db_state = DBState::default();
+        let mut epic = Epic::new("Release".to_owned(), "".to_owned());
+        epic.due = NaiveDate::from_ymd_opt(2026, 12, 1);
+        db_state
+            .epics
+            .insert(1, epic);
+        let mut story = Story::new("Fix login; again, and again".to_owned(), "".to_owned());
+        story.due = NaiveDate::from_ymd_opt(2026, 11, 1);
+        db_state
+            .stories
+            .insert(2, story);
+        db_state
+            .stories
+            .insert(3, Story::new("no due date".to_owned(), "".to_owned()));
+        db_state
+            .sprints
+            .insert(
+                4,
+                Sprint::new(
+                    "Sprint".to_owned(),
+                    "ship it".to_owned(),
+                    NaiveDate::from_ymd_opt(2026, 11, 2),
+                    None,
+                ),
+            );
+        db_state
+    }
+
+    fn stamp() -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2026, 10, 1)
+            .unwrap()
+            .and_hms_opt(8, 30, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn render_ics_should_add_an_event_per_date() {
+        let ics = render_ics(&board(), stamp());
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(
+            ics.matches("BEGIN:VEVENT")
+                .count(),
+            3
+        );
+        assert!(ics.contains(
+            "UID:story-2-due@rust-jira-cli\r\nDTSTAMP:20261001T083000Z\r\nDTSTART;VALUE=DATE:20261101\r\nDTEND;VALUE=DATE:20261102\r\nSUMMARY:Story 2: Fix login\\; again\\, and again (due)\r\n"
+        ));
+        assert!(ics.contains("UID:epic-1-due@rust-jira-cli\r\n"));
+        assert!(ics.contains("UID:sprint-4-start@rust-jira-cli\r\n"));
+        assert!(!ics.contains("sprint-4-end"));
+        assert!(ics.contains("DESCRIPTION:status: OPEN\r\n"));
+    }
+
+    #[test]
+    fn render_ics_should_keep_uids_stable() {
+        let later = stamp() + Days::new(3);
+        let uids = |ics: String| -> Vec<String> {
+            ics.lines()
+                .filter(|line| line.starts_with("UID:"))
+                .map(str::to_owned)
+                .collect()
+        };
+
+        assert_eq!(
+            uids(render_ics(&board(), stamp())),
+            uids(render_ics(&board(), later))
+        );
+    }
+
+    #[test]
+    fn fold_should_split_long_lines_on_character_boundaries() {
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+
+        let folded = fold(&line);
+
+        let parts: Vec<&str> = folded
+            .split("\r\n")
+            .collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts
+            .iter()
+            .all(|part| part.len() <= LINE_LIMIT));
+        assert_eq!(
+            parts.concat(),
+            format!("SUMMARY:{} {}", "é".repeat(33), "é".repeat(7))
+        );
+    }
+}
@@ -0,0 +1,25 @@
+mod graph;
+mod html;
+mod ics;
+mod svg;
+
+pub use graph::*;
+pub use html::*;
+pub use ics::*;
+pub use svg::*;
+
+// markup escaping shared by the html and svg exports
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
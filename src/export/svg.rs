@@ -0,0 +1,266 @@
+use std::fmt::Write;
+use std::fs;
+
+use anyhow::Result;
+
+use crate::charts::{
+    epic_burndown, sprint_burndown, velocity, Burndown, VelocityBar, VELOCITY_SPRINTS,
+};
+use crate::dates::today;
+use crate::db::JiraHandle;
+
+use super::escape;
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 360.0;
+// room for the axis labels around the plot
+const LEFT: f64 = 48.0;
+const RIGHT: f64 = 16.0;
+const TOP: f64 = 40.0;
+const BOTTOM: f64 = 40.0;
+
+/// The charts that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chart {
+    EpicBurndown(u32),
+    SprintBurndown(u32),
+    Velocity,
+}
+
+pub fn export_svg(db: &JiraHandle, chart: Chart, file_path: &str) -> Result<()> {
+    let db_state = db.read_full_record()?;
+    let today = today();
+    let svg = match chart {
+        Chart::EpicBurndown(epic_id) => burndown_svg(&epic_burndown(&db_state, epic_id, today)?),
+        Chart::SprintBurndown(sprint_id) => {
+            burndown_svg(&sprint_burndown(&db_state, sprint_id, today)?)
+        }
+        Chart::Velocity => velocity_svg(&velocity(&db_state, VELOCITY_SPRINTS)),
+    };
+    fs::write(file_path, svg)?;
+    Ok(())
+}
+
+fn open_svg(title: &str) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {WIDTH} {HEIGHT}\" font-family=\"Helvetica, Arial, sans-serif\" font-size=\"12\">"
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"{WIDTH}\" height=\"{HEIGHT}\" fill=\"#ffffff\"/>"
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{LEFT}\" y=\"24\" font-size=\"16\" font-weight=\"600\">{}</text>",
+        escape(title)
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{LEFT}\" y1=\"{TOP}\" x2=\"{LEFT}\" y2=\"{}\" stroke=\"#57606a\"/>",
+        HEIGHT - BOTTOM
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{LEFT}\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"#57606a\"/>",
+        HEIGHT - BOTTOM,
+        WIDTH - RIGHT
+    );
+    svg
+}
+
+// y coordinate of a value on an axis running from 0 to max
+fn y_of(value: f64, max: u64) -> f64 {
+    let plot = HEIGHT - TOP - BOTTOM;
+    HEIGHT - BOTTOM - value / max.max(1) as f64 * plot
+}
+
+fn y_label(svg: &mut String, max: u64) {
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{max}</text>",
+        LEFT - 6.0,
+        TOP + 4.0
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">0</text>",
+        LEFT - 6.0,
+        HEIGHT - BOTTOM + 4.0
+    );
+}
+
+/// Remaining points as a line, with the ideal line dashed when the range has
+/// a planned end.
+pub fn burndown_svg(burndown: &Burndown) -> String {
+    let mut svg = open_svg(&burndown.title);
+    let max = burndown
+        .remaining
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(burndown.total);
+    y_label(&mut svg, max);
+
+    let last = burndown
+        .days
+        .len()
+        .saturating_sub(1);
+    let x_of = |day: usize| LEFT + day as f64 / last.max(1) as f64 * (WIDTH - LEFT - RIGHT);
+
+    if let (Some(start), Some(end)) = (burndown.ideal_at(0), burndown.ideal_at(last)) {
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#8c959f\" stroke-dasharray=\"6 4\"/>",
+            x_of(0),
+            y_of(start, max),
+            x_of(last),
+            y_of(end, max)
+        );
+    }
+
+    let points: Vec<String> = burndown
+        .remaining
+        .iter()
+        .enumerate()
+        .filter_map(|(day, remaining)| {
+            remaining
+                .map(|remaining| format!("{:.1},{:.1}", x_of(day), y_of(remaining as f64, max)))
+        })
+        .collect();
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#0969da\" stroke-width=\"2\"/>",
+        points.join(" ")
+    );
+
+    if let (Some(first), Some(last)) = (
+        burndown
+            .days
+            .first(),
+        burndown
+            .days
+            .last(),
+    ) {
+        let _ = writeln!(
+            svg,
+            "<text x=\"{LEFT}\" y=\"{}\">{first}</text>",
+            HEIGHT - BOTTOM + 18.0
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{last}</text>",
+            WIDTH - RIGHT,
+            HEIGHT - BOTTOM + 18.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Committed and completed points side by side for each sprint.
+pub fn velocity_svg(bars: &[VelocityBar]) -> String {
+    let mut svg = open_svg("Velocity");
+    let max = bars
+        .iter()
+        .map(|bar| bar.committed)
+        .max()
+        .unwrap_or_default();
+    y_label(&mut svg, max);
+
+    let slot = (WIDTH - LEFT - RIGHT)
+        / bars
+            .len()
+            .max(1) as f64;
+    let bar_width = slot * 0.35;
+    for (i, bar) in bars
+        .iter()
+        .enumerate()
+    {
+        let x = LEFT + i as f64 * slot + slot * 0.15;
+        for (offset, value, color) in [
+            (0.0, bar.committed, "#d0d7de"),
+            (bar_width, bar.completed, "#1a7f37"),
+        ] {
+            let y = y_of(value as f64, max);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{color}\"/>",
+                x + offset,
+                y,
+                bar_width,
+                HEIGHT - BOTTOM - y
+            );
+        }
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+            x + bar_width,
+            HEIGHT - BOTTOM + 18.0,
+            escape(&bar.name)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn burndown_svg_should_draw_remaining_and_ideal_lines() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let burndown = Burndown {
+            title: "Sprint <1>".to_owned(),
+            days: start
+                .iter_days()
+                .take(3)
+                .collect(),
+            remaining: vec![Some(10), Some(5), None],
+            total: 10,
+            ideal: true,
+        };
+
+        let svg = burndown_svg(&burndown);
+
+        assert!(svg.contains("Sprint &lt;1&gt;"));
+        assert!(svg.contains("<polyline points=\"48.0,40.0 336.0,180.0\""));
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.contains(">2024-01-03</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn velocity_svg_should_draw_a_bar_pair_per_sprint() {
+        let bars = [
+            VelocityBar {
+                sprint_id: 1,
+                name: "one".to_owned(),
+                committed: 8,
+                completed: 8,
+            },
+            VelocityBar {
+                sprint_id: 2,
+                name: "two".to_owned(),
+                committed: 8,
+                completed: 4,
+            },
+        ];
+
+        let svg = velocity_svg(&bars);
+
+        assert_eq!(
+            svg.matches("<rect x=")
+                .count(),
+            4
+        );
+        assert!(svg.contains(">two</text>"));
+    }
+}
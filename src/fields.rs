@@ -0,0 +1,341 @@
+//! Custom fields teams define in the config, such as a customer or the
+//! environment a bug was found in.
+//!
+//! Values are stored on epics and stories as text in a normalized form, so
+//! changing a definition never breaks loading the board:
+//!
+//! ```json
+//! { "fields": [
+//!     { "name": "customer", "type": "text" },
+//!     { "name": "environment", "type": "enum", "options": ["staging", "production"] }
+//! ] }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::dates::parse_date;
+use crate::error::{Error, Result};
+
+// names the editor form already uses for built-in fields
+const RESERVED_NAMES: [&str; 5] = ["name", "priority", "type", "severity", "labels"];
+
+/// Kind of value a custom field holds.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    Text,
+    Number,
+    Enum,
+    Date,
+    Bool,
+}
+
+/// A custom field from the config.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FieldDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: FieldKind,
+    /// Allowed values of an enum field.
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+impl FieldDef {
+    pub fn new(name: &str, kind: FieldKind) -> Self {
+        FieldDef {
+            name: name.to_owned(),
+            kind,
+            options: vec![],
+        }
+    }
+
+    /// What the field expects, for prompts and the editor form.
+    pub fn hint(&self) -> String {
+        match self.kind {
+            FieldKind::Text => "text".to_owned(),
+            FieldKind::Number => "number".to_owned(),
+            FieldKind::Enum => format!(
+                "one of {}",
+                self.options
+                    .join(", ")
+            ),
+            FieldKind::Date => "date, e.g. 2026-11-01, +3d or friday".to_owned(),
+            FieldKind::Bool => "yes or no".to_owned(),
+        }
+    }
+
+    /// Normalized value of `input`, or `None` when it is empty so the field
+    /// gets cleared.
+    pub fn parse(&self, input: &str, today: NaiveDate) -> Result<Option<String>> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        let invalid = || {
+            Error::Validation(format!(
+                "{}: expected {} but found `{}`",
+                self.name,
+                self.hint(),
+                input
+            ))
+        };
+        let value = match self.kind {
+            FieldKind::Text => input.to_owned(),
+            // stored in one form so `2.50` and `2.5` compare equal
+            FieldKind::Number => input
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .ok_or_else(invalid)?
+                .to_string(),
+            FieldKind::Enum => self
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(input))
+                .ok_or_else(invalid)?
+                .clone(),
+            FieldKind::Date => parse_date(input, today)
+                .map_err(|_| invalid())?
+                .to_string(),
+            FieldKind::Bool => match input
+                .to_lowercase()
+                .as_str()
+            {
+                "yes" | "y" | "true" => "yes".to_owned(),
+                "no" | "n" | "false" => "no".to_owned(),
+                _ => return Err(invalid()),
+            },
+        };
+        Ok(Some(value))
+    }
+}
+
+/// Checks that field names are unique, do not shadow built-in fields and
+/// that only enum fields list options.
+pub fn validate_defs(defs: &[FieldDef]) -> std::result::Result<(), String> {
+    let mut names = HashSet::new();
+    for def in defs {
+        let name = def
+            .name
+            .trim();
+        if name.is_empty()
+            || name.contains(['=', ':', '#'])
+            || RESERVED_NAMES.contains(
+                &name
+                    .to_lowercase()
+                    .as_str(),
+            )
+        {
+            return Err(format!("invalid field name `{}`", def.name));
+        }
+        if !names.insert(name.to_lowercase()) {
+            return Err(format!("field `{}` is defined twice", name));
+        }
+        match (
+            def.kind,
+            def.options
+                .is_empty(),
+        ) {
+            (FieldKind::Enum, true) => return Err(format!("enum field `{}` needs options", name)),
+            (FieldKind::Enum, false) | (_, true) => {}
+            (_, false) => return Err(format!("only enum fields take options, not `{}`", name)),
+        }
+    }
+    Ok(())
+}
+
+/// `name=value` filter on custom fields; an empty value matches items
+/// without the field. The value is normalized like stored values, so
+/// `billable=y` matches `yes` and `launch=friday` matches its date.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldFilter {
+    pub name: String,
+    pub value: String,
+}
+
+impl FieldFilter {
+    pub fn parse(input: &str, defs: &[FieldDef], today: NaiveDate) -> Result<FieldFilter> {
+        let (name, value) = input
+            .split_once('=')
+            .ok_or_else(|| {
+                Error::Validation(format!("expected `field=value` but found `{}`", input))
+            })?;
+        let name = name.trim();
+        let def = defs
+            .iter()
+            .find(|def| {
+                def.name
+                    .eq_ignore_ascii_case(name)
+            })
+            .ok_or_else(|| Error::Validation(format!("unknown field `{}`", name)))?;
+        Ok(FieldFilter {
+            name: def
+                .name
+                .clone(),
+            value: def
+                .parse(value, today)?
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn matches(&self, fields: &BTreeMap<String, String>) -> bool {
+        match fields.get(&self.name) {
+            Some(value) => value.eq_ignore_ascii_case(&self.value),
+            None => self
+                .value
+                .is_empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+    }
+
+    #[test]
+    fn parse_should_normalize_values_per_kind() {
+        let environment = FieldDef {
+            options: vec!["staging".to_owned(), "production".to_owned()],
+            ..FieldDef::new("environment", FieldKind::Enum)
+        };
+
+        assert_eq!(
+            environment
+                .parse("Production", today())
+                .unwrap(),
+            Some("production".to_owned())
+        );
+        assert_eq!(
+            FieldDef::new("launch", FieldKind::Date)
+                .parse("+1w", today())
+                .unwrap(),
+            Some("2026-10-21".to_owned())
+        );
+        assert_eq!(
+            FieldDef::new("billable", FieldKind::Bool)
+                .parse("Y", today())
+                .unwrap(),
+            Some("yes".to_owned())
+        );
+        assert_eq!(
+            FieldDef::new("effort", FieldKind::Number)
+                .parse(" 2.5 ", today())
+                .unwrap(),
+            Some("2.5".to_owned())
+        );
+        assert_eq!(
+            FieldDef::new("customer", FieldKind::Text)
+                .parse("  ", today())
+                .unwrap(),
+            None
+        );
+        assert!(matches!(
+            environment.parse("qa", today()),
+            Err(Error::Validation(message))
+                if message == "environment: expected one of staging, production but found `qa`"
+        ));
+        assert!(FieldDef::new("effort", FieldKind::Number)
+            .parse("lots", today())
+            .is_err());
+    }
+
+    #[test]
+    fn validate_defs_should_reject_duplicates_and_stray_options() {
+        let customer = FieldDef::new("customer", FieldKind::Text);
+
+        assert_eq!(validate_defs(std::slice::from_ref(&customer)), Ok(()));
+        assert_eq!(
+            validate_defs(&[
+                customer.clone(),
+                FieldDef::new("Customer", FieldKind::Number)
+            ]),
+            Err("field `Customer` is defined twice".to_owned())
+        );
+        assert_eq!(
+            validate_defs(&[FieldDef::new("Priority", FieldKind::Text)]),
+            Err("invalid field name `Priority`".to_owned())
+        );
+        assert_eq!(
+            validate_defs(&[FieldDef::new("labels", FieldKind::Text)]),
+            Err("invalid field name `labels`".to_owned())
+        );
+        assert_eq!(
+            validate_defs(&[FieldDef::new("environment", FieldKind::Enum)]),
+            Err("enum field `environment` needs options".to_owned())
+        );
+        assert_eq!(
+            validate_defs(&[FieldDef {
+                options: vec!["a".to_owned()],
+                ..customer
+            }]),
+            Err("only enum fields take options, not `customer`".to_owned())
+        );
+    }
+
+    #[test]
+    fn filter_should_match_values_and_missing_fields() {
+        let defs = [FieldDef::new("customer", FieldKind::Text)];
+        let fields = BTreeMap::from([("customer".to_owned(), "Acme".to_owned())]);
+
+        let filter = FieldFilter::parse("Customer = acme", &defs, today()).unwrap();
+        assert!(filter.matches(&fields));
+        assert!(!filter.matches(&BTreeMap::new()));
+
+        let unset = FieldFilter::parse("customer=", &defs, today()).unwrap();
+        assert!(!unset.matches(&fields));
+        assert!(unset.matches(&BTreeMap::new()));
+
+        assert!(FieldFilter::parse("owner=me", &defs, today()).is_err());
+        assert!(FieldFilter::parse("customer", &defs, today()).is_err());
+    }
+
+    #[test]
+    fn filter_should_normalize_values_like_stored_ones() {
+        let defs = [
+            FieldDef::new("billable", FieldKind::Bool),
+            FieldDef::new("launch", FieldKind::Date),
+            FieldDef::new("effort", FieldKind::Number),
+        ];
+        let store = |name: &str, input: &str| {
+            let def = defs
+                .iter()
+                .find(|def| def.name == name)
+                .unwrap();
+            BTreeMap::from([(
+                name.to_owned(),
+                def.parse(input, today())
+                    .unwrap()
+                    .unwrap(),
+            )])
+        };
+
+        for input in ["billable=true", "billable=y", "billable=Yes"] {
+            assert!(FieldFilter::parse(input, &defs, today())
+                .unwrap()
+                .matches(&store("billable", "yes")));
+        }
+        assert!(!FieldFilter::parse("billable=no", &defs, today())
+            .unwrap()
+            .matches(&store("billable", "yes")));
+        // 2026-10-14 is a Wednesday
+        assert!(FieldFilter::parse("launch=friday", &defs, today())
+            .unwrap()
+            .matches(&store("launch", "2026-10-16")));
+        assert!(FieldFilter::parse("effort=2.50", &defs, today())
+            .unwrap()
+            .matches(&store("effort", "2.5")));
+        assert!(matches!(
+            FieldFilter::parse("billable=maybe", &defs, today()),
+            Err(Error::Validation(_))
+        ));
+    }
+}
@@ -1,15 +1,184 @@
-use std::io;
+use std::fs;
+use std::io::{self, Write};
+use std::process::Command;
 
-pub fn get_user_input() -> String {
-    let mut user_input = String::new();
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::queue;
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 
-    io::stdin()
-        .read_line(&mut user_input)
-        .unwrap();
+use crate::ui::{display_width, truncate_to_width, wrap_text};
 
-    user_input
+// blocks until a key is pressed; key releases and repeats reported by some
+// terminals are skipped so every key is handled once
+pub fn read_key() -> io::Result<KeyEvent> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(key);
+            }
+        }
+    }
 }
 
-pub fn wait_for_key_press() {
-    io::stdin().read_line(&mut String::new()).unwrap();
-}
\ No newline at end of file
+pub fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c')
+        && key
+            .modifiers
+            .contains(KeyModifiers::CONTROL)
+}
+
+// modal prompts are drawn as a box over the current page; the page is
+// redrawn by the tui once the prompt returns
+
+// asks for a line of text; escape cancels
+pub fn prompt_line(title: &str, label: &str) -> io::Result<Option<String>> {
+    let mut input = String::new();
+
+    loop {
+        draw_modal(title, label, Some(&input), "[enter] confirm | [esc] cancel")?;
+
+        let key = read_key()?;
+        match key.code {
+            _ if is_interrupt(&key) => return Ok(None),
+            KeyCode::Enter => return Ok(Some(input)),
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    }
+}
+
+// asks for a single key, e.g. a menu choice; escape cancels
+pub fn prompt_key(title: &str, label: &str) -> io::Result<Option<char>> {
+    draw_modal(title, label, None, "[esc] cancel")?;
+
+    loop {
+        let key = read_key()?;
+        match key.code {
+            _ if is_interrupt(&key) => return Ok(None),
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char(c) => return Ok(Some(c)),
+            _ => {}
+        }
+    }
+}
+
+// $VISUAL or $EDITOR, if either is set
+pub fn editor_command() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|command| {
+            !command
+                .trim()
+                .is_empty()
+        })
+}
+
+// opens `contents` in the editor and returns the saved text, or None when the
+// editor exits with an error; the terminal is handed over to the editor
+// while it runs
+pub fn edit_text(editor: &str, contents: &str) -> io::Result<Option<String>> {
+    let mut file = tempfile::Builder::new()
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(contents.as_bytes())?;
+    file.flush()?;
+
+    // the command may carry arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words
+        .next()
+        .unwrap_or(editor);
+
+    let mut stdout = io::stdout();
+    execute!(stdout, Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    let status = Command::new(program)
+        .args(words)
+        .arg(file.path())
+        .status();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+
+    if !status?.success() {
+        return Ok(None);
+    }
+    fs::read_to_string(file.path()).map(Some)
+}
+
+fn draw_modal(title: &str, label: &str, input: Option<&str>, hint: &str) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let inner_width = (columns as usize)
+        .saturating_sub(8)
+        .clamp(20, 70);
+    let label_lines = wrap_text(label, inner_width).len();
+    let height = label_lines + if input.is_some() { 5 } else { 4 };
+    let left = (columns as usize).saturating_sub(inner_width + 4) / 2;
+    let top = (rows as usize).saturating_sub(height) / 2;
+
+    let fit = |text: &str| -> String {
+        let (visible, used) = truncate_to_width(text, inner_width);
+        format!("{}{}", visible, " ".repeat(inner_width - used))
+    };
+    let title = format!(" {} ", title);
+    let border: String = "─".repeat(inner_width + 2 - display_width(&title).min(inner_width));
+
+    let mut lines = vec![format!("┌{}{}┐", title, border)];
+    for label_line in wrap_text(label, inner_width) {
+        lines.push(format!("│ {} │", fit(&label_line)));
+    }
+    let input_row = top + lines.len();
+    // keep the end of long input visible next to the cursor
+    let visible_input = input.map(|input| tail_to_width(input, inner_width - 2));
+    if let Some(visible) = visible_input {
+        lines.push(format!("│ {} │", fit(&format!("> {}", visible))));
+    }
+    lines.push(format!("│ {} │", fit("")));
+    lines.push(format!("│ {} │", fit(hint)));
+    lines.push(format!("└{}┘", "─".repeat(inner_width + 2)));
+
+    let mut stdout = io::stdout();
+    for (i, line) in lines
+        .iter()
+        .enumerate()
+    {
+        queue!(
+            stdout,
+            MoveTo(left as u16, (top + i) as u16),
+            SetAttribute(Attribute::Bold),
+            Print(line),
+            SetAttribute(Attribute::Reset)
+        )?;
+    }
+
+    match visible_input {
+        Some(visible) => {
+            let cursor = display_width(visible);
+            queue!(
+                stdout,
+                MoveTo((left + 4 + cursor) as u16, input_row as u16),
+                Show
+            )?;
+        }
+        None => queue!(stdout, Hide)?,
+    }
+    stdout.flush()
+}
+
+// longest suffix of the text that fits in `width` cells
+fn tail_to_width(text: &str, width: usize) -> &str {
+    let mut tail = text;
+    while display_width(tail) > width {
+        let mut chars = tail.chars();
+        chars.next();
+        tail = chars.as_str();
+    }
+    tail
+}
@@ -0,0 +1,44 @@
+//! Epics and stories tracker backed by a JSON file.
+//!
+//! [`db::JiraHandle`] is the entry point for reading and changing the board,
+//! [`models`] holds the data it stores and every fallible call returns an
+//! [`error::Error`]. [`server`] and [`rpc`] serve a board to other
+//! processes; the remaining modules are the front ends the `rust-jira-cli`
+//! binary is built from.
+//!
+//! ```no_run
+//! use rust_jira_cli::db::JiraHandle;
+//! use rust_jira_cli::models::{Epic, Status, Story};
+//!
+//! # fn main() -> rust_jira_cli::error::Result<()> {
+//! let db = JiraHandle::new("./data/db.json".to_owned());
+//! let epic_id = db.create_epic(Epic::new("Release".to_owned(), "".to_owned()))?;
+//! let story_id = db.create_story(Story::new("Changelog".to_owned(), "".to_owned()), epic_id)?;
+//! db.update_story_status(story_id, Status::InProgress)?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod charts;
+pub mod config;
+pub mod dates;
+pub mod db;
+pub mod error;
+pub mod export;
+pub mod fields;
+pub mod models;
+pub mod reports;
+pub mod templates;
+
+// front end internals of the binary, public only so it can be built from
+// the library; they are not part of the documented API
+#[doc(hidden)]
+pub mod io_utils;
+#[doc(hidden)]
+pub mod navigator;
+pub mod rpc;
+pub mod server;
+#[doc(hidden)]
+pub mod tui;
+#[doc(hidden)]
+pub mod ui;
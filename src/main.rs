@@ -1,54 +1,170 @@
 use std::rc::Rc;
-mod models;
 
-mod db;
+use rust_jira_cli::config::Config;
+use rust_jira_cli::db::JiraHandle;
+use rust_jira_cli::error::Error;
+use rust_jira_cli::export::{Chart, GraphFormat};
+use rust_jira_cli::{export, rpc, server, tui};
 
-mod ui;
+const DB_PATH: &str = "./data/db.json";
+const CONFIG_PATH: &str = "./data/config.json";
 
-mod io_utils;
-use db::JiraHandle;
-use io_utils::*;
-
-mod navigator;
-use navigator::*;
+const GRAPH_USAGE: &str = "rust-jira-cli export-graph <dot | mermaid> [epic ID] [file]";
+const SVG_USAGE: &str = "rust-jira-cli export-svg <epic ID | sprint ID | velocity> [file]";
 
 fn main() {
-    let db = Rc::new(JiraHandle::new("./data/db.json".to_owned()));
-    let mut navigator = Navigator::new(Rc::clone(&db));
-
-    loop {
-        clearscreen::clear().unwrap();
-
-        if let Some(page) = navigator.get_current_page() {
-            if let Err(error) = page.draw_page() {
-                println!(
-                    "Error rendering page: {}\nPress any key to continue...",
-                    error
-                );
-                wait_for_key_press();
+    let db = Rc::new(JiraHandle::new(DB_PATH.to_owned()));
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .collect();
+
+    match args
+        .first()
+        .map(String::as_str)
+    {
+        Some("export-html") => {
+            let file_path = args
+                .get(1)
+                .map(String::as_str)
+                .unwrap_or("./board.html");
+            match export::export_html(&db, file_path) {
+                Ok(()) => println!("Board exported to {}", file_path),
+                Err(error) => exit_with_error("Error exporting board", error),
+            }
+        }
+        Some("export-graph") => {
+            let (format, default_path) = match args
+                .get(1)
+                .map(String::as_str)
+            {
+                Some("dot") => (GraphFormat::Dot, "./board.dot"),
+                Some("mermaid") => (GraphFormat::Mermaid, "./board.mmd"),
+                _ => exit_with_usage(GRAPH_USAGE),
             };
+            let (epic_id, rest) = match args
+                .get(2)
+                .map(String::as_str)
+            {
+                Some("epic") => match args
+                    .get(3)
+                    .map(|id| id.parse::<u32>())
+                {
+                    Some(Ok(epic_id)) => (Some(epic_id), 4),
+                    _ => exit_with_usage(GRAPH_USAGE),
+                },
+                _ => (None, 2),
+            };
+            let file_path = args
+                .get(rest)
+                .map(String::as_str)
+                .unwrap_or(default_path);
+            match export::export_graph(&db, format, epic_id, file_path) {
+                Ok(()) => println!("Graph exported to {}", file_path),
+                Err(error) => exit_with_error("Error exporting graph", error),
+            }
+        }
+        Some("export-ics") => {
+            let file_path = args
+                .get(1)
+                .map(String::as_str)
+                .unwrap_or("./calendar.ics");
+            match export::export_ics(&db, file_path) {
+                Ok(()) => println!("Calendar exported to {}", file_path),
+                Err(error) => exit_with_error("Error exporting calendar", error),
+            }
+        }
+        Some("export-svg") => {
+            let (chart, default_path, rest) = match (
+                args.get(1)
+                    .map(String::as_str),
+                args.get(2)
+                    .map(|id| id.parse::<u32>()),
+            ) {
+                (Some("epic"), Some(Ok(id))) => (Chart::EpicBurndown(id), "./burndown.svg", 3),
+                (Some("sprint"), Some(Ok(id))) => (Chart::SprintBurndown(id), "./burndown.svg", 3),
+                (Some("velocity"), _) => (Chart::Velocity, "./velocity.svg", 2),
+                _ => exit_with_usage(SVG_USAGE),
+            };
+            let file_path = args
+                .get(rest)
+                .map(String::as_str)
+                .unwrap_or(default_path);
+            match export::export_svg(&db, chart, file_path) {
+                Ok(()) => println!("Chart exported to {}", file_path),
+                Err(error) => exit_with_error("Error exporting chart", error),
+            }
+        }
+        Some("serve") => {
+            let addr = args
+                .get(1)
+                .map(String::as_str)
+                .unwrap_or("127.0.0.1:8080");
+            if let Err(error) = server::serve(&db, addr) {
+                exit_with_error("Error serving board", error);
+            }
+        }
+        Some("rpc") => {
+            let stdin = std::io::stdin();
+            if let Err(error) = rpc::run(&db, stdin.lock(), std::io::stdout()) {
+                exit_with_error("Error running JSON-RPC session", error);
+            }
+        }
+        Some(command) => {
+            eprintln!(
+                "Unknown command: {}\nUsage: rust-jira-cli [export-graph <dot | mermaid> [epic ID] [file] | export-html [file] | export-ics [file] | export-svg <epic ID | sprint ID | velocity> [file] | serve [addr] | rpc]",
+                command
+            );
+            std::process::exit(1);
+        }
+        None => {
+            if let Err(error) = db.read_full_record() {
+                exit_with_error("Error opening board", error.into());
+            }
+            let config = match Config::load(CONFIG_PATH) {
+                Ok(config) => config,
+                Err(error) => exit_with_error("Error loading config", error.into()),
+            };
+            if let Err(error) = tui::run(db, &config) {
+                exit_with_error("Error running board", error);
+            }
+        }
+    }
+}
+
+fn exit_with_usage(usage: &str) -> ! {
+    eprintln!("Usage: {}", usage);
+    std::process::exit(1);
+}
+
+// library errors get a hint and an exit code per variant so scripts can tell
+// a missing item from a broken database file
+fn exit_with_error(context: &str, error: anyhow::Error) -> ! {
+    eprintln!("{}: {}", context, error);
 
-            let user_input = get_user_input();
-
-            match page.handle_input(user_input.trim()) {
-                Err(error) => {
-                    println!(
-                        "Error getting user input: {}\nPress any key to continue...",
-                        error
-                    );
-                    wait_for_key_press();
-                }
-                Ok(action) => {
-                    if let Some(action) = action {
-                        if let Err(error) = navigator.handle_action(action) {
-                            println!("Error handling processing user input: {}\nPress any key to continue...", error);
-                            wait_for_key_press();
-                        }
-                    }
-                }
-            }
-        } else {
-            break;
+    let code = match error.downcast_ref::<Error>() {
+        Some(error) => {
+            if let Some(hint) = error_hint(error) {
+                eprintln!("{}", hint);
+            }
+            error.exit_code()
         }
+        None => 1,
+    };
+    std::process::exit(code);
+}
+
+fn error_hint(error: &Error) -> Option<String> {
+    match error {
+        Error::NotFound { kind, .. } => Some(format!("Check the {} id and try again.", kind)),
+        Error::ProjectNotFound(_) => Some("Check the project key and try again.".to_owned()),
+        Error::Io(_) => Some(format!(
+            "Make sure {} exists and is readable and writable.",
+            DB_PATH
+        )),
+        Error::Corrupt(_) => Some(format!(
+            "Fix {} by hand or restore it from a backup.",
+            DB_PATH
+        )),
+        Error::Conflict(_) | Error::Validation(_) => None,
     }
 }
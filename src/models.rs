@@ -1,6 +1,11 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
 
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+/// Navigation and editing requests produced by the interactive pages.
 #[derive(Debug, PartialEq, Eq)]
 pub enum Action {
     NavigateToEpicDetail { epic_id: u32 },
@@ -8,14 +13,50 @@ pub enum Action {
     NavigateToPreviousPage,
     CreateEpic,
     UpdateEpicStatus { epic_id: u32 },
+    UpdateEpicPriority { epic_id: u32 },
+    UpdateEpicDue { epic_id: u32 },
+    UpdateEpicField { epic_id: u32 },
+    FilterEpicStories { epic_id: u32 },
     DeleteEpic { epic_id: u32 },
     CreateStory { epic_id: u32 },
     UpdateStoryStatus { story_id: u32 },
+    UpdateStoryPriority { story_id: u32 },
+    UpdateStoryPoints { story_id: u32 },
+    UpdateStoryDue { story_id: u32 },
+    UpdateStoryField { story_id: u32 },
+    ToggleTimer { story_id: u32 },
+    LinkStory { story_id: u32 },
+    UnlinkStory { story_id: u32 },
     DeleteStory { epic_id: u32, story_id: u32 },
+    CreateSubTask { story_id: u32 },
+    ToggleSubTask { subtask_id: u32 },
+    DeleteSubTask { story_id: u32, subtask_id: u32 },
+    NavigateToBacklog,
+    NavigateToBacklogStory { story_id: u32 },
+    CreateBacklogStory,
+    AttachStory { story_id: u32 },
+    DeleteBacklogStory { story_id: u32 },
+    NavigateToSprints,
+    NavigateToSprintPlanning { sprint_id: u32 },
+    NavigateToActiveSprint,
+    CreateSprint,
+    AddStoryToSprint { sprint_id: u32, story_id: u32 },
+    RemoveStoryFromSprint { sprint_id: u32, story_id: u32 },
+    StartSprint { sprint_id: u32 },
+    CompleteSprint { sprint_id: u32 },
+    NavigateToEpicCharts { epic_id: u32 },
+    NavigateToSprintCharts { sprint_id: u32 },
+    NavigateToAgenda,
+    NavigateToTimeReport,
+    NavigateToProjects,
+    // None opens the epics of every project
+    NavigateToProject { key: Option<String> },
+    CreateProject,
     Exit,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+/// Workflow state shared by epics and stories.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum Status {
     Open,
     InProgress,
@@ -23,6 +64,20 @@ pub enum Status {
     Closed,
 }
 
+impl Status {
+    pub const ALL: [Status; 4] = [
+        Status::Open,
+        Status::InProgress,
+        Status::Resolved,
+        Status::Closed,
+    ];
+
+    // resolved and closed items count as done for progress reporting
+    pub fn is_done(&self) -> bool {
+        matches!(self, Status::Resolved | Status::Closed)
+    }
+}
+
 impl Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -41,44 +96,527 @@ impl Display for Status {
         }
     }
 }
+/// How urgent an epic or story is; boards written before priorities existed
+/// load as [`Priority::Medium`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 3] = [Priority::Low, Priority::Medium, Priority::High];
+}
+
+impl Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "LOW"),
+            Self::Medium => write!(f, "MEDIUM"),
+            Self::High => write!(f, "HIGH"),
+        }
+    }
+}
+
+/// A group of stories; `stories` holds the ids of its children.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Epic {
+    /// Human key such as `API-42`; unset outside of projects.
+    #[serde(default)]
+    pub key: Option<ItemKey>,
+    /// Short title.
     pub name: String,
     pub description: String,
     pub status: Status,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Unset for items created before timestamps were recorded.
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Day the epic is due, in local time.
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    /// Custom field values by field name, see [`crate::fields`].
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Free-form tags, e.g. set by a template.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Ids of the stories of this epic, in the order they were added.
     pub stories: Vec<u32>,
 }
 
 impl Epic {
+    /// An open epic without stories.
     pub fn new(name: String, description: String) -> Self {
         Epic {
-            name: name,
-            description: description,
+            key: None,
+            name,
+            description,
             status: Status::Open,
+            priority: Priority::default(),
+            created_at: Some(Utc::now()),
+            due: None,
+            fields: BTreeMap::new(),
+            labels: vec![],
             stories: vec![],
         }
     }
 }
 
+/// What kind of work a story describes.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum IssueType {
+    #[default]
+    Story,
+    Bug,
+    Task,
+    Spike,
+}
+
+impl IssueType {
+    pub const ALL: [IssueType; 4] = [
+        IssueType::Story,
+        IssueType::Bug,
+        IssueType::Task,
+        IssueType::Spike,
+    ];
+}
+
+impl Display for IssueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Story => write!(f, "STORY"),
+            Self::Bug => write!(f, "BUG"),
+            Self::Task => write!(f, "TASK"),
+            Self::Spike => write!(f, "SPIKE"),
+        }
+    }
+}
+
+/// How badly a bug affects users.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum Severity {
+    Minor,
+    #[default]
+    Major,
+    Critical,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 3] = [Severity::Minor, Severity::Major, Severity::Critical];
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Minor => write!(f, "MINOR"),
+            Self::Major => write!(f, "MAJOR"),
+            Self::Critical => write!(f, "CRITICAL"),
+        }
+    }
+}
+
+/// Fields only bugs carry.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct BugDetails {
+    pub steps_to_reproduce: String,
+    pub severity: Severity,
+}
+
+/// A unit of work belonging to at most one epic; stories without one make
+/// up the backlog.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Story {
+    /// Human key such as `API-42`; unset outside of projects.
+    #[serde(default)]
+    pub key: Option<ItemKey>,
+    /// Short title.
     pub name: String,
+    /// Markdown text; `- [ ]` lines count as a checklist.
     pub description: String,
+    /// Current status; changes made through [`crate::db::JiraHandle`] are
+    /// also recorded in `history`.
     pub status: Status,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Unset for items created before timestamps were recorded.
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Day the story is due, in local time.
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    /// Custom field values by field name, see [`crate::fields`].
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Free-form tags, e.g. set by a template.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Ids of the sub-tasks of this story.
+    #[serde(default)]
+    pub subtasks: Vec<u32>,
+    /// Stories written before issue types existed load as
+    /// [`IssueType::Story`].
+    #[serde(default)]
+    pub issue_type: IssueType,
+    /// Set for stories of type [`IssueType::Bug`].
+    #[serde(default)]
+    pub bug: Option<BugDetails>,
+    /// Estimate used by the burndown and velocity charts; unestimated
+    /// stories count as zero.
+    #[serde(default)]
+    pub points: Option<u32>,
+    /// Every status change, oldest first.
+    #[serde(default)]
+    pub history: Vec<StatusChange>,
+    /// Time spent on the story, oldest first.
+    #[serde(default)]
+    pub worklogs: Vec<Worklog>,
 }
 
 impl Story {
+    /// An open story.
     pub fn new(name: String, description: String) -> Self {
         Story {
+            key: None,
             name,
             description,
             status: Status::Open,
+            priority: Priority::default(),
+            created_at: Some(Utc::now()),
+            due: None,
+            fields: BTreeMap::new(),
+            labels: vec![],
+            subtasks: vec![],
+            issue_type: IssueType::default(),
+            bug: None,
+            points: None,
+            history: vec![],
+            worklogs: vec![],
         }
     }
+
+    /// Like [`Story::new`], but a bug with the given details.
+    pub fn new_bug(name: String, description: String, bug: BugDetails) -> Self {
+        Story {
+            issue_type: IssueType::Bug,
+            bug: Some(bug),
+            ..Story::new(name, description)
+        }
+    }
+
+    /// Status the story had at `at` according to its history; stories
+    /// without a history are assumed to have always had their current
+    /// status.
+    pub fn status_at(&self, at: DateTime<Utc>) -> Status {
+        if self
+            .history
+            .is_empty()
+        {
+            return self.status;
+        }
+        self.history
+            .iter()
+            .rev()
+            .find(|change| change.at <= at)
+            .map_or(Status::Open, |change| change.status)
+    }
 }
+
+/// Entry of [`Story::history`].
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct StatusChange {
+    pub status: Status,
+    pub at: DateTime<Utc>,
+}
+
+/// Entry of [`Story::worklogs`].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Worklog {
+    pub user: String,
+    pub start: DateTime<Utc>,
+    pub minutes: u32,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// A running timer; stopping it logs the time on its story.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Timer {
+    pub story_id: u32,
+    pub started_at: DateTime<Utc>,
+}
+
+/// A checklist entry belonging to exactly one story.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct SubTask {
+    pub name: String,
+    pub status: Status,
+    #[serde(default)]
+    pub assignee: Option<String>,
+}
+
+impl SubTask {
+    pub fn new(name: String, assignee: Option<String>) -> Self {
+        SubTask {
+            name,
+            status: Status::Open,
+            assignee,
+        }
+    }
+}
+/// Lifecycle of a sprint; at most one sprint is active at a time.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SprintState {
+    #[default]
+    Planned,
+    Active,
+    Completed,
+}
+
+impl Display for SprintState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Planned => write!(f, "PLANNED"),
+            Self::Active => write!(f, "ACTIVE"),
+            Self::Completed => write!(f, "COMPLETED"),
+        }
+    }
+}
+
+/// How one story relates to another.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum LinkKind {
+    Blocks,
+    /// Only used when creating links; stored as the inverse [`LinkKind::Blocks`].
+    IsBlockedBy,
+    RelatesTo,
+    Duplicates,
+}
+
+impl LinkKind {
+    pub const ALL: [LinkKind; 4] = [
+        LinkKind::Blocks,
+        LinkKind::IsBlockedBy,
+        LinkKind::RelatesTo,
+        LinkKind::Duplicates,
+    ];
+
+    /// How the link reads from the story it points to.
+    pub fn inverse(&self) -> &'static str {
+        match self {
+            Self::Blocks => "is blocked by",
+            Self::IsBlockedBy => "blocks",
+            Self::RelatesTo => "relates to",
+            Self::Duplicates => "is duplicated by",
+        }
+    }
+}
+
+impl Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blocks => write!(f, "blocks"),
+            Self::IsBlockedBy => write!(f, "is blocked by"),
+            Self::RelatesTo => write!(f, "relates to"),
+            Self::Duplicates => write!(f, "duplicates"),
+        }
+    }
+}
+
+/// A typed link from one story to another, e.g. `from` blocks `to`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Link {
+    pub from: u32,
+    pub to: u32,
+    pub kind: LinkKind,
+}
+
+/// A group of epics and stories whose items are numbered in their own
+/// sequence.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Project {
+    pub name: String,
+    /// Number of the last item keyed in this project.
+    #[serde(default)]
+    pub last_number: u32,
+}
+
+impl Project {
+    pub fn new(name: String) -> Self {
+        Project {
+            name,
+            last_number: 0,
+        }
+    }
+}
+
+/// Project key and number of an epic or story, shown as `API-42`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ItemKey {
+    pub project: String,
+    pub number: u32,
+}
+
+impl Display for ItemKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.project, self.number)
+    }
+}
+
+/// A time box of work; `stories` holds the ids of the stories planned into
+/// it and is kept as a record once the sprint is completed.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct Sprint {
+    pub name: String,
+    pub goal: String,
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+    #[serde(default)]
+    pub state: SprintState,
+    pub stories: Vec<u32>,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Sprint {
+    pub fn new(
+        name: String,
+        goal: String,
+        start: Option<NaiveDate>,
+        end: Option<NaiveDate>,
+    ) -> Self {
+        Sprint {
+            name,
+            goal,
+            start,
+            end,
+            state: SprintState::Planned,
+            stories: vec![],
+            started_at: None,
+            completed_at: None,
+        }
+    }
+}
+
+/// The whole board as it is persisted; epic, story, sub-task and sprint ids
+/// share the `last_item_id` sequence, while the keys of epics and stories
+/// are numbered per project.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone)]
 pub struct DBState {
+    /// Last id handed out; the next item gets `last_item_id + 1`.
     pub last_item_id: u32,
+    /// Epics by id.
     pub epics: HashMap<u32, Epic>,
+    /// Stories by id.
     pub stories: HashMap<u32, Story>,
+    /// Sub-tasks by id.
+    #[serde(default)]
+    pub subtasks: HashMap<u32, SubTask>,
+    /// Sprints by id.
+    #[serde(default)]
+    pub sprints: HashMap<u32, Sprint>,
+    /// Running timers by user, at most one each.
+    #[serde(default)]
+    pub timers: HashMap<String, Timer>,
+    /// Links between stories, stored once from the story they start at.
+    #[serde(default)]
+    pub links: Vec<Link>,
+    /// Projects by key, e.g. `API`.
+    #[serde(default)]
+    pub projects: BTreeMap<String, Project>,
+}
+
+impl DBState {
+    /// Id of the epic the story belongs to, `None` for backlog stories.
+    pub fn epic_of_story(&self, story_id: u32) -> Option<u32> {
+        self.epics
+            .iter()
+            .find(|(_, epic)| {
+                epic.stories
+                    .contains(&story_id)
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Id of the sprint that is currently running, if any.
+    pub fn active_sprint(&self) -> Option<u32> {
+        self.sprints
+            .iter()
+            .find(|(_, sprint)| sprint.state == SprintState::Active)
+            .map(|(id, _)| *id)
+    }
+
+    /// Id of the planned sprint that comes next, the one with the earliest
+    /// start date and then the lowest id.
+    pub fn next_planned_sprint(&self) -> Option<u32> {
+        self.sprints
+            .iter()
+            .filter(|(_, sprint)| sprint.state == SprintState::Planned)
+            .min_by_key(|(id, sprint)| {
+                (
+                    sprint
+                        .start
+                        .is_none(),
+                    sprint.start,
+                    **id,
+                )
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Id of the planned or active sprint the story is part of, if any.
+    pub fn open_sprint_of_story(&self, story_id: u32) -> Option<u32> {
+        self.sprints
+            .iter()
+            .find(|(_, sprint)| {
+                sprint.state != SprintState::Completed
+                    && sprint
+                        .stories
+                        .contains(&story_id)
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Ids of the stories blocking the story that are not done yet, in id
+    /// order; the story counts as blocked while there are any.
+    pub fn open_blockers(&self, story_id: u32) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .links
+            .iter()
+            .filter(|link| link.kind == LinkKind::Blocks && link.to == story_id)
+            .map(|link| link.from)
+            .filter(|id| {
+                self.stories
+                    .get(id)
+                    .is_some_and(|story| {
+                        !story
+                            .status
+                            .is_done()
+                    })
+            })
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// Ids of the stories that are not attached to any epic, in id order.
+    pub fn backlog(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .stories
+            .keys()
+            .copied()
+            .filter(|id| {
+                self.epic_of_story(*id)
+                    .is_none()
+            })
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
 }
@@ -2,47 +2,89 @@ use anyhow::{anyhow, Context, Ok, Result};
 use std::rc::Rc;
 
 use crate::{
+    config::Config,
     db::JiraHandle,
-    models::Action,
-    ui::{EpicDetail, HomePage, Page, Prompts, StoryDetail},
+    models::{Action, Status},
+    ui::{
+        ActiveSprint, AgendaPage, BacklogPage, ChartPage, ChartScope, EpicDetail, HomePage, Page,
+        ProjectsPage, Prompts, SprintPlanning, SprintsPage, StoryDetail, TimeReportPage,
+    },
 };
 
 pub struct Navigator {
     pages: Vec<Box<dyn Page>>,
     prompts: Prompts,
     db: Rc<JiraHandle>,
+    page_size: usize,
+    // worklogs are recorded under this name
+    user: String,
 }
 
 impl Navigator {
     pub fn new(db: Rc<JiraHandle>) -> Self {
+        Navigator::with_config(db, &Config::default())
+    }
+
+    /// Starts on the project picker once the board has projects, otherwise
+    /// on the epics.
+    pub fn with_config(db: Rc<JiraHandle>, config: &Config) -> Self {
+        let has_projects = db
+            .read_full_record()
+            .is_ok_and(|db_state| {
+                !db_state
+                    .projects
+                    .is_empty()
+            });
+        let start: Box<dyn Page> = if has_projects {
+            Box::new(ProjectsPage::new(Rc::clone(&db)))
+        } else {
+            Box::new(HomePage::with_page_size(Rc::clone(&db), config.page_size))
+        };
         Self {
-            pages: vec![Box::new(HomePage { db: Rc::clone(&db) })],
-            prompts: Prompts::new(),
+            pages: vec![start],
+            prompts: Prompts::with_config(config),
             db,
+            page_size: config.page_size,
+            user: config.user_name(),
         }
     }
 
-    pub fn get_current_page(&self) -> Option<&Box<dyn Page>> {
+    pub fn get_current_page(&self) -> Option<&dyn Page> {
         self.pages
             .last()
+            .map(|page| page.as_ref())
+    }
+
+    /// Leaves the current page for the one below it; the first page is kept
+    /// so going back never quits the board.
+    pub fn go_back(&mut self) {
+        if self
+            .pages
+            .len()
+            > 1
+        {
+            self.pages
+                .pop();
+        }
     }
 
     pub fn handle_action(&mut self, action: Action) -> Result<()> {
         match action {
             Action::NavigateToEpicDetail { epic_id } => {
                 self.pages
-                    .push(Box::new(EpicDetail {
+                    .push(Box::new(EpicDetail::with_page_size(
                         epic_id,
-                        db: Rc::clone(&self.db),
-                    }));
+                        Rc::clone(&self.db),
+                        self.page_size,
+                    )));
             }
             Action::NavigateToStoryDetail { epic_id, story_id } => {
                 self.pages
-                    .push(Box::new(StoryDetail {
+                    .push(Box::new(StoryDetail::new(
                         epic_id,
                         story_id,
-                        db: Rc::clone(&self.db),
-                    }));
+                        Rc::clone(&self.db),
+                    )));
             }
             Action::NavigateToPreviousPage => {
                 if !self
@@ -54,12 +96,25 @@ impl Navigator {
                 }
             }
             Action::CreateEpic => {
-                let epic = (self
+                if let Some((epic, stories)) = (self
                     .prompts
-                    .create_epic)();
-                self.db
-                    .create_epic(epic)
+                    .create_epic)()
+                {
+                    let epic_id = match self.current_project() {
+                        Some(project) => self
+                            .db
+                            .create_project_epic(&project, epic),
+                        None => self
+                            .db
+                            .create_epic(epic),
+                    }
                     .with_context(|| anyhow!("failed to create epic!"))?;
+                    for story in stories {
+                        self.db
+                            .create_story(story, epic_id)
+                            .with_context(|| anyhow!("failed to create story!"))?;
+                    }
+                }
             }
             Action::UpdateEpicStatus { epic_id } => {
                 let status = (self
@@ -72,6 +127,62 @@ impl Navigator {
                         .with_context(|| anyhow!("failed to update epic!"))?;
                 }
             }
+            Action::UpdateEpicPriority { epic_id } => {
+                if let Some(priority) = (self
+                    .prompts
+                    .update_priority)()
+                {
+                    self.db
+                        .update_epic_priority(epic_id, priority)
+                        .with_context(|| anyhow!("failed to update epic!"))?;
+                }
+            }
+            Action::UpdateEpicDue { epic_id } => {
+                if let Some(due) = (self
+                    .prompts
+                    .update_due)()
+                {
+                    self.db
+                        .update_epic_due(epic_id, due)
+                        .with_context(|| anyhow!("failed to update epic!"))?;
+                }
+            }
+            Action::UpdateEpicField { epic_id } => {
+                let db_state = self
+                    .db
+                    .read_full_record()?;
+                let epic = db_state
+                    .epics
+                    .get(&epic_id)
+                    .ok_or_else(|| anyhow!("could not find epic!"))?;
+                if let Some((name, value)) = (self
+                    .prompts
+                    .update_field)(&epic.fields)
+                {
+                    self.db
+                        .update_epic_field(epic_id, &name, value)
+                        .with_context(|| anyhow!("failed to update epic!"))?;
+                }
+            }
+            Action::FilterEpicStories { epic_id } => {
+                if let Some(filter) = (self
+                    .prompts
+                    .field_filter)()
+                {
+                    // the filter belongs to the epic page it was asked from
+                    if let Some(page) = self
+                        .pages
+                        .last()
+                        .and_then(|page| {
+                            page.as_any()
+                                .downcast_ref::<EpicDetail>()
+                        })
+                        .filter(|page| page.epic_id == epic_id)
+                    {
+                        page.set_field_filter(filter);
+                    }
+                }
+            }
             Action::DeleteEpic { epic_id } => {
                 if (self
                     .prompts
@@ -91,12 +202,14 @@ impl Navigator {
                 }
             }
             Action::CreateStory { epic_id } => {
-                let story = (self
+                if let Some(story) = (self
                     .prompts
-                    .create_story)();
-                self.db
-                    .create_story(story, epic_id)
-                    .with_context(|| anyhow!("failed to create story!"))?;
+                    .create_story)()
+                {
+                    self.db
+                        .create_story(story, epic_id)
+                        .with_context(|| anyhow!("failed to create story!"))?;
+                }
             }
             Action::UpdateStoryStatus { story_id } => {
                 let status = (self
@@ -104,11 +217,113 @@ impl Navigator {
                     .update_status)();
 
                 if let Some(status) = status {
+                    if status == Status::InProgress {
+                        let blockers = self
+                            .db
+                            .read_full_record()?
+                            .open_blockers(story_id);
+                        if !blockers.is_empty()
+                            && !(self
+                                .prompts
+                                .start_blocked)(&blockers)
+                        {
+                            return Ok(());
+                        }
+                    }
                     self.db
                         .update_story_status(story_id, status)
                         .with_context(|| anyhow!("failed to update story!"))?;
                 }
             }
+            Action::UpdateStoryPriority { story_id } => {
+                if let Some(priority) = (self
+                    .prompts
+                    .update_priority)()
+                {
+                    self.db
+                        .update_story_priority(story_id, priority)
+                        .with_context(|| anyhow!("failed to update story!"))?;
+                }
+            }
+            Action::UpdateStoryPoints { story_id } => {
+                if let Some(points) = (self
+                    .prompts
+                    .update_points)()
+                {
+                    self.db
+                        .update_story_points(story_id, points)
+                        .with_context(|| anyhow!("failed to update story!"))?;
+                }
+            }
+            Action::UpdateStoryDue { story_id } => {
+                if let Some(due) = (self
+                    .prompts
+                    .update_due)()
+                {
+                    self.db
+                        .update_story_due(story_id, due)
+                        .with_context(|| anyhow!("failed to update story!"))?;
+                }
+            }
+            Action::UpdateStoryField { story_id } => {
+                let db_state = self
+                    .db
+                    .read_full_record()?;
+                let story = db_state
+                    .stories
+                    .get(&story_id)
+                    .ok_or_else(|| anyhow!("could not find story!"))?;
+                if let Some((name, value)) = (self
+                    .prompts
+                    .update_field)(&story.fields)
+                {
+                    self.db
+                        .update_story_field(story_id, &name, value)
+                        .with_context(|| anyhow!("failed to update story!"))?;
+                }
+            }
+            Action::ToggleTimer { story_id } => {
+                let running = self
+                    .db
+                    .read_full_record()?
+                    .timers
+                    .get(&self.user)
+                    .map(|timer| timer.story_id);
+                if running == Some(story_id) {
+                    if let Some(note) = (self
+                        .prompts
+                        .worklog_note)()
+                    {
+                        self.db
+                            .stop_timer(&self.user, note)
+                            .with_context(|| anyhow!("failed to stop timer!"))?;
+                    }
+                } else {
+                    self.db
+                        .start_timer(story_id, &self.user)
+                        .with_context(|| anyhow!("failed to start timer!"))?;
+                }
+            }
+            Action::LinkStory { story_id } => {
+                if let Some((kind, other_id)) = (self
+                    .prompts
+                    .link_story)()
+                {
+                    self.db
+                        .link_stories(story_id, kind, other_id)
+                        .with_context(|| anyhow!("failed to link stories!"))?;
+                }
+            }
+            Action::UnlinkStory { story_id } => {
+                if let Some(other_id) = (self
+                    .prompts
+                    .unlink_story)()
+                {
+                    self.db
+                        .unlink_stories(story_id, other_id)
+                        .with_context(|| anyhow!("failed to unlink stories!"))?;
+                }
+            }
             Action::DeleteStory { epic_id, story_id } => {
                 if (self
                     .prompts
@@ -127,6 +342,231 @@ impl Navigator {
                     }
                 }
             }
+            Action::CreateSubTask { story_id } => {
+                if let Some(subtask) = (self
+                    .prompts
+                    .create_subtask)()
+                {
+                    self.db
+                        .create_subtask(subtask, story_id)
+                        .with_context(|| anyhow!("failed to create sub-task!"))?;
+                }
+            }
+            Action::ToggleSubTask { subtask_id } => {
+                self.db
+                    .toggle_subtask(subtask_id)
+                    .with_context(|| anyhow!("failed to update sub-task!"))?;
+            }
+            Action::DeleteSubTask {
+                story_id,
+                subtask_id,
+            } => {
+                if (self
+                    .prompts
+                    .delete_subtask)()
+                {
+                    self.db
+                        .delete_subtask(story_id, subtask_id)
+                        .with_context(|| anyhow!("failed to delete sub-task!"))?;
+                }
+            }
+            Action::NavigateToBacklog => {
+                self.pages
+                    .push(Box::new(BacklogPage::in_project(
+                        Rc::clone(&self.db),
+                        self.current_project(),
+                        self.page_size,
+                    )));
+            }
+            Action::NavigateToBacklogStory { story_id } => {
+                self.pages
+                    .push(Box::new(StoryDetail::in_backlog(
+                        story_id,
+                        Rc::clone(&self.db),
+                    )));
+            }
+            Action::CreateBacklogStory => {
+                if let Some(story) = (self
+                    .prompts
+                    .create_story)()
+                {
+                    match self.current_project() {
+                        Some(project) => self
+                            .db
+                            .create_project_backlog_story(&project, story),
+                        None => self
+                            .db
+                            .create_backlog_story(story),
+                    }
+                    .with_context(|| anyhow!("failed to create story!"))?;
+                }
+            }
+            Action::AttachStory { story_id } => {
+                if let Some(epic_id) = (self
+                    .prompts
+                    .attach_story)()
+                {
+                    self.db
+                        .attach_story(story_id, epic_id)
+                        .with_context(|| anyhow!("failed to attach story!"))?;
+                }
+            }
+            Action::DeleteBacklogStory { story_id } => {
+                if (self
+                    .prompts
+                    .delete_story)()
+                {
+                    self.db
+                        .delete_backlog_story(story_id)
+                        .with_context(|| anyhow!("failed to delete story!"))?;
+
+                    if !self
+                        .pages
+                        .is_empty()
+                    {
+                        self.pages
+                            .pop();
+                    }
+                }
+            }
+            Action::NavigateToSprints => {
+                self.pages
+                    .push(Box::new(SprintsPage::new(Rc::clone(&self.db))));
+            }
+            Action::NavigateToSprintPlanning { sprint_id } => {
+                self.pages
+                    .push(Box::new(SprintPlanning::new(
+                        sprint_id,
+                        Rc::clone(&self.db),
+                    )));
+            }
+            Action::NavigateToActiveSprint => {
+                self.pages
+                    .push(Box::new(ActiveSprint::new(Rc::clone(&self.db))));
+            }
+            Action::CreateSprint => {
+                if let Some(sprint) = (self
+                    .prompts
+                    .create_sprint)()
+                {
+                    self.db
+                        .create_sprint(sprint)
+                        .with_context(|| anyhow!("failed to create sprint!"))?;
+                }
+            }
+            Action::AddStoryToSprint {
+                sprint_id,
+                story_id,
+            } => {
+                self.db
+                    .add_story_to_sprint(sprint_id, story_id)
+                    .with_context(|| anyhow!("failed to plan story!"))?;
+            }
+            Action::RemoveStoryFromSprint {
+                sprint_id,
+                story_id,
+            } => {
+                self.db
+                    .remove_story_from_sprint(sprint_id, story_id)
+                    .with_context(|| anyhow!("failed to unplan story!"))?;
+            }
+            Action::StartSprint { sprint_id } => {
+                self.db
+                    .start_sprint(sprint_id)
+                    .with_context(|| anyhow!("failed to start sprint!"))?;
+            }
+            Action::CompleteSprint { sprint_id } => {
+                let unfinished = self
+                    .db
+                    .complete_sprint(sprint_id)
+                    .with_context(|| anyhow!("failed to complete sprint!"))?;
+
+                if !unfinished.is_empty()
+                    && (self
+                        .prompts
+                        .roll_over)(&unfinished)
+                {
+                    // the next planned sprint, or a new one when none is
+                    // planned yet
+                    let next = match self
+                        .db
+                        .read_full_record()?
+                        .next_planned_sprint()
+                    {
+                        Some(next) => next,
+                        None => match (self
+                            .prompts
+                            .create_sprint)()
+                        {
+                            Some(sprint) => self
+                                .db
+                                .create_sprint(sprint)
+                                .with_context(|| anyhow!("failed to create sprint!"))?,
+                            None => return Ok(()),
+                        },
+                    };
+                    self.db
+                        .roll_over_stories(sprint_id, next)
+                        .with_context(|| anyhow!("failed to roll over stories!"))?;
+                }
+            }
+            Action::NavigateToEpicCharts { epic_id } => {
+                self.pages
+                    .push(Box::new(ChartPage::new(
+                        ChartScope::Epic(epic_id),
+                        Rc::clone(&self.db),
+                    )));
+            }
+            Action::NavigateToSprintCharts { sprint_id } => {
+                self.pages
+                    .push(Box::new(ChartPage::new(
+                        ChartScope::Sprint(sprint_id),
+                        Rc::clone(&self.db),
+                    )));
+            }
+            Action::NavigateToAgenda => {
+                self.pages
+                    .push(Box::new(AgendaPage::new(Rc::clone(&self.db))));
+            }
+            Action::NavigateToTimeReport => {
+                self.pages
+                    .push(Box::new(TimeReportPage::new(Rc::clone(&self.db))));
+            }
+            Action::NavigateToProjects => {
+                // the picker sits below every other page
+                match self
+                    .pages
+                    .iter()
+                    .position(|page| {
+                        page.as_any()
+                            .is::<ProjectsPage>()
+                    }) {
+                    Some(index) => self
+                        .pages
+                        .truncate(index + 1),
+                    None => {
+                        self.pages = vec![Box::new(ProjectsPage::new(Rc::clone(&self.db)))];
+                    }
+                }
+            }
+            Action::NavigateToProject { key } => {
+                self.pages
+                    .push(Box::new(HomePage::in_project(
+                        Rc::clone(&self.db),
+                        key,
+                        self.page_size,
+                    )));
+            }
+            Action::CreateProject => {
+                if let Some((key, name)) = (self
+                    .prompts
+                    .create_project)()
+                {
+                    self.db
+                        .create_project(&key, name)
+                        .with_context(|| anyhow!("failed to create project!"))?;
+                }
+            }
             Action::Exit => self
                 .pages
                 .clear(),
@@ -135,25 +575,47 @@ impl Navigator {
         Ok(())
     }
 
+    // the project of the epics page the current page was opened from;
+    // new epics and backlog stories are created in it
+    fn current_project(&self) -> Option<String> {
+        self.pages
+            .iter()
+            .rev()
+            .find_map(|page| {
+                page.as_any()
+                    .downcast_ref::<HomePage>()
+            })
+            .and_then(|page| {
+                page.project
+                    .clone()
+            })
+    }
+
     // Private functions used for testing
 
+    #[cfg(test)]
     fn get_page_count(&self) -> usize {
         self.pages
             .len()
     }
 
+    #[cfg(test)]
     fn set_prompts(&mut self, prompts: Prompts) {
         self.prompts = prompts;
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
+    use crate::ui::Frame;
     use crate::{
         db::tests::test_utils::MockDB,
-        models::{Epic, Status, Story},
+        fields::FieldFilter,
+        models::{Epic, LinkKind, Priority, Sprint, SprintState, Story, SubTask},
     };
+    use chrono::NaiveDate;
 
     #[test]
     fn should_start_on_home_page() {
@@ -242,6 +704,28 @@ mod tests {
         assert_eq!(nav.get_page_count(), 0);
     }
 
+    #[test]
+    fn go_back_should_keep_the_first_page() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+
+        let mut nav = Navigator::new(db);
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id: 1 })
+            .unwrap();
+
+        nav.go_back();
+        assert_eq!(nav.get_page_count(), 1);
+        nav.go_back();
+        assert_eq!(nav.get_page_count(), 1);
+        assert!(nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<HomePage>()
+            .is_some());
+    }
+
     #[test]
     fn handle_action_should_clear_pages_on_exit() {
         let db = Rc::new(JiraHandle {
@@ -272,7 +756,12 @@ mod tests {
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.create_epic = Box::new(|| Epic::new("name".to_owned(), "description".to_owned()));
+        prompts.create_epic = Box::new(|| {
+            Some((
+                Epic::new("name".to_owned(), "description".to_owned()),
+                vec![Story::new("changelog".to_owned(), "".to_owned())],
+            ))
+        });
 
         nav.set_prompts(prompts);
 
@@ -297,6 +786,37 @@ mod tests {
             .1;
         assert_eq!(epic.name, "name".to_owned());
         assert_eq!(epic.description, "description".to_owned());
+        // stories from the epic template are created inside the new epic
+        assert_eq!(
+            epic.stories
+                .len(),
+            1
+        );
+        assert_eq!(db_state.stories[&epic.stories[0]].name, "changelog");
+    }
+
+    #[test]
+    fn handle_action_should_skip_create_epic_when_cancelled() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.create_epic = Box::new(|| None);
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::CreateEpic)
+            .unwrap();
+
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        assert!(db_state
+            .epics
+            .is_empty());
     }
 
     #[test]
@@ -305,7 +825,7 @@ mod tests {
             database: Box::new(MockDB::new()),
         });
         let epic_id = db
-            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
             .unwrap();
 
         let mut nav = Navigator::new(Rc::clone(&db));
@@ -331,13 +851,391 @@ mod tests {
         );
     }
 
+    #[test]
+    fn handle_action_should_handle_update_story_priority() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_priority = Box::new(|| Some(Priority::High));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateStoryPriority { story_id })
+            .unwrap();
+
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        assert_eq!(db_state.stories[&story_id].priority, Priority::High);
+    }
+
+    #[test]
+    fn handle_action_should_handle_due_dates() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_due = Box::new(|| Some(NaiveDate::from_ymd_opt(2026, 11, 1)));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateEpicDue { epic_id })
+            .unwrap();
+        nav.handle_action(Action::UpdateStoryDue { story_id })
+            .unwrap();
+        nav.handle_action(Action::NavigateToAgenda)
+            .unwrap();
+
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        assert_eq!(
+            db_state.epics[&epic_id].due,
+            NaiveDate::from_ymd_opt(2026, 11, 1)
+        );
+        assert_eq!(
+            db_state.stories[&story_id].due,
+            NaiveDate::from_ymd_opt(2026, 11, 1)
+        );
+        assert!(nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .is::<AgendaPage>());
+    }
+
+    #[test]
+    fn handle_action_should_set_and_filter_by_custom_fields() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.update_field = Box::new(|_| Some(("customer".to_owned(), Some("acme".to_owned()))));
+        prompts.field_filter = Box::new(|| {
+            Some(Some(FieldFilter {
+                name: "customer".to_owned(),
+                value: "acme".to_owned(),
+            }))
+        });
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::UpdateStoryField { story_id })
+            .unwrap();
+        nav.handle_action(Action::NavigateToEpicDetail { epic_id })
+            .unwrap();
+        nav.handle_action(Action::FilterEpicStories { epic_id })
+            .unwrap();
+
+        assert_eq!(
+            db.read_full_record()
+                .unwrap()
+                .stories[&story_id]
+                .fields["customer"],
+            "acme"
+        );
+        let mut frame = Frame::default();
+        nav.get_current_page()
+            .unwrap()
+            .draw_page(&mut frame)
+            .unwrap();
+        let lines = frame.lines();
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with(&format!("{} ", story_id))));
+        assert!(!lines
+            .iter()
+            .any(|line| line.starts_with(&format!("{} ", story_id + 1))));
+        assert!(lines
+            .iter()
+            .any(|line| line.contains("| where: customer=acme")));
+    }
+
+    #[test]
+    fn handle_action_should_start_and_stop_timers() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let story_id = db
+            .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+            .unwrap();
+        let config = Config {
+            user: Some("alice".to_owned()),
+            ..Config::default()
+        };
+
+        let mut nav = Navigator::with_config(Rc::clone(&db), &config);
+
+        let mut prompts = Prompts::new();
+        prompts.worklog_note = Box::new(|| Some("pairing".to_owned()));
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::ToggleTimer { story_id })
+            .unwrap();
+        assert_eq!(
+            db.read_full_record()
+                .unwrap()
+                .timers["alice"]
+                .story_id,
+            story_id
+        );
+
+        nav.handle_action(Action::ToggleTimer { story_id })
+            .unwrap();
+
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        assert!(db_state
+            .timers
+            .is_empty());
+        let worklogs = &db_state.stories[&story_id].worklogs;
+        assert_eq!(worklogs.len(), 1);
+        assert_eq!(worklogs[0].user, "alice");
+        assert_eq!(worklogs[0].note, "pairing");
+    }
+
+    #[test]
+    fn handle_action_should_warn_before_starting_blocked_stories() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let story_id = db
+            .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+            .unwrap();
+        let blocker = db
+            .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.link_story = Box::new(move || Some((LinkKind::IsBlockedBy, blocker)));
+        prompts.update_status = Box::new(|| Some(Status::InProgress));
+        prompts.start_blocked = Box::new(|_| false);
+
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::LinkStory { story_id })
+            .unwrap();
+        nav.handle_action(Action::UpdateStoryStatus { story_id })
+            .unwrap();
+        assert_eq!(
+            db.read_full_record()
+                .unwrap()
+                .stories[&story_id]
+                .status,
+            Status::Open
+        );
+
+        // blockers that are done no longer hold the story back
+        db.update_story_status(blocker, Status::Closed)
+            .unwrap();
+        nav.handle_action(Action::UpdateStoryStatus { story_id })
+            .unwrap();
+        assert_eq!(
+            db.read_full_record()
+                .unwrap()
+                .stories[&story_id]
+                .status,
+            Status::InProgress
+        );
+    }
+
+    #[test]
+    fn handle_action_should_handle_subtasks() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.create_subtask = Box::new(|| Some(SubTask::new("task".to_owned(), None)));
+        prompts.delete_subtask = Box::new(|| true);
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::CreateSubTask { story_id })
+            .unwrap();
+        let subtask_id = db
+            .read_full_record()
+            .unwrap()
+            .stories[&story_id]
+            .subtasks[0];
+
+        nav.handle_action(Action::ToggleSubTask { subtask_id })
+            .unwrap();
+        assert_eq!(
+            db.read_full_record()
+                .unwrap()
+                .subtasks[&subtask_id]
+                .status,
+            Status::Resolved
+        );
+
+        nav.handle_action(Action::DeleteSubTask {
+            story_id,
+            subtask_id,
+        })
+        .unwrap();
+        assert!(db
+            .read_full_record()
+            .unwrap()
+            .subtasks
+            .is_empty());
+    }
+
+    #[test]
+    fn handle_action_should_handle_backlog_stories() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+        let mut prompts = Prompts::new();
+        prompts.create_story = Box::new(|| Some(Story::new("chore".to_owned(), "".to_owned())));
+        prompts.attach_story = Box::new(move || Some(epic_id));
+        prompts.delete_story = Box::new(|| true);
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::NavigateToBacklog)
+            .unwrap();
+        assert!(nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .downcast_ref::<BacklogPage>()
+            .is_some());
+
+        nav.handle_action(Action::CreateBacklogStory)
+            .unwrap();
+        nav.handle_action(Action::CreateBacklogStory)
+            .unwrap();
+        let backlog = db
+            .read_full_record()
+            .unwrap()
+            .backlog();
+        assert_eq!(backlog.len(), 2);
+
+        nav.handle_action(Action::AttachStory {
+            story_id: backlog[0],
+        })
+        .unwrap();
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        assert_eq!(db_state.epics[&epic_id].stories, vec![backlog[0]]);
+        assert_eq!(db_state.backlog(), vec![backlog[1]]);
+
+        nav.handle_action(Action::NavigateToBacklogStory {
+            story_id: backlog[1],
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 3);
+        nav.handle_action(Action::DeleteBacklogStory {
+            story_id: backlog[1],
+        })
+        .unwrap();
+        assert_eq!(nav.get_page_count(), 2);
+        assert!(db
+            .read_full_record()
+            .unwrap()
+            .backlog()
+            .is_empty());
+    }
+
+    #[test]
+    fn handle_action_should_roll_unfinished_stories_into_a_new_sprint() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let story_id = db
+            .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+            .unwrap();
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+        let mut prompts = Prompts::new();
+        prompts.create_sprint =
+            Box::new(|| Some(Sprint::new("sprint".to_owned(), "".to_owned(), None, None)));
+        prompts.roll_over = Box::new(move |unfinished| unfinished == [story_id]);
+        nav.set_prompts(prompts);
+
+        nav.handle_action(Action::CreateSprint)
+            .unwrap();
+        let sprint_id = db
+            .read_full_record()
+            .unwrap()
+            .next_planned_sprint()
+            .unwrap();
+        nav.handle_action(Action::AddStoryToSprint {
+            sprint_id,
+            story_id,
+        })
+        .unwrap();
+        nav.handle_action(Action::StartSprint { sprint_id })
+            .unwrap();
+        nav.handle_action(Action::CompleteSprint { sprint_id })
+            .unwrap();
+
+        // no sprint was planned, so completing asked for a new one
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        let next_id = db_state
+            .next_planned_sprint()
+            .unwrap();
+        assert_ne!(next_id, sprint_id);
+        assert_eq!(db_state.sprints[&next_id].stories, vec![story_id]);
+        assert_eq!(db_state.sprints[&sprint_id].state, SprintState::Completed);
+    }
+
     #[test]
     fn handle_action_should_handle_delete_epic() {
         let db = Rc::new(JiraHandle {
             database: Box::new(MockDB::new()),
         });
         let epic_id = db
-            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
             .unwrap();
 
         let mut nav = Navigator::new(Rc::clone(&db));
@@ -367,13 +1265,14 @@ mod tests {
             database: Box::new(MockDB::new()),
         });
         let epic_id = db
-            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
             .unwrap();
 
         let mut nav = Navigator::new(Rc::clone(&db));
 
         let mut prompts = Prompts::new();
-        prompts.create_story = Box::new(|| Story::new("name".to_owned(), "description".to_owned()));
+        prompts.create_story =
+            Box::new(|| Some(Story::new("name".to_owned(), "description".to_owned())));
 
         nav.set_prompts(prompts);
 
@@ -406,10 +1305,10 @@ mod tests {
             database: Box::new(MockDB::new()),
         });
         let epic_id = db
-            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
             .unwrap();
         let story_id = db
-            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
             .unwrap();
 
         let mut nav = Navigator::new(Rc::clone(&db));
@@ -441,10 +1340,10 @@ mod tests {
             database: Box::new(MockDB::new()),
         });
         let epic_id = db
-            .create_epic(Epic::new("".to_owned(), "".to_owned()))
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
             .unwrap();
         let story_id = db
-            .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
             .unwrap();
 
         let mut nav = Navigator::new(Rc::clone(&db));
@@ -467,4 +1366,83 @@ mod tests {
             0
         );
     }
+
+    #[test]
+    fn handle_action_should_create_items_in_the_picked_project() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+
+        let mut nav = Navigator::new(Rc::clone(&db));
+
+        let mut prompts = Prompts::new();
+        prompts.create_project = Box::new(|| Some(("API".to_owned(), "backend".to_owned())));
+        prompts.create_epic = Box::new(|| {
+            Some((
+                Epic::new("release".to_owned(), "".to_owned()),
+                vec![Story::new("changelog".to_owned(), "".to_owned())],
+            ))
+        });
+        prompts.create_story = Box::new(|| Some(Story::new("spike".to_owned(), "".to_owned())));
+        nav.set_prompts(prompts);
+
+        // the home page gives way to the picker, which is then the bottom page
+        nav.handle_action(Action::NavigateToProjects)
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+        nav.handle_action(Action::CreateProject)
+            .unwrap();
+        nav.handle_action(Action::NavigateToProject {
+            key: Some("API".to_owned()),
+        })
+        .unwrap();
+        nav.handle_action(Action::CreateEpic)
+            .unwrap();
+        nav.handle_action(Action::NavigateToBacklog)
+            .unwrap();
+        nav.handle_action(Action::CreateBacklogStory)
+            .unwrap();
+
+        let db_state = db
+            .read_full_record()
+            .unwrap();
+        let mut keys: Vec<String> = db_state
+            .epics
+            .values()
+            .filter_map(|epic| {
+                epic.key
+                    .as_ref()
+            })
+            .chain(
+                db_state
+                    .stories
+                    .values()
+                    .filter_map(|story| {
+                        story
+                            .key
+                            .as_ref()
+                    }),
+            )
+            .map(ToString::to_string)
+            .collect();
+        keys.sort();
+        assert_eq!(keys, ["API-1", "API-2", "API-3"]);
+
+        nav.handle_action(Action::NavigateToProjects)
+            .unwrap();
+        assert_eq!(nav.get_page_count(), 1);
+        assert!(nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .is::<ProjectsPage>());
+
+        // a board with projects opens on the picker
+        let nav = Navigator::new(db);
+        assert!(nav
+            .get_current_page()
+            .unwrap()
+            .as_any()
+            .is::<ProjectsPage>());
+    }
 }
@@ -0,0 +1,183 @@
+//! Time reports built from the worklogs of stories.
+
+use std::collections::BTreeMap;
+
+use crate::models::{DBState, Story};
+
+/// Logged time of one epic, or of the backlog when `epic_id` is `None`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EpicTime {
+    pub epic_id: Option<u32>,
+    pub name: String,
+    pub minutes: u64,
+}
+
+/// Logged time of one user.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UserTime {
+    pub user: String,
+    pub minutes: u64,
+}
+
+// summed as u64 so many long worklogs cannot overflow
+fn logged_minutes(story: &Story) -> u64 {
+    story
+        .worklogs
+        .iter()
+        .map(|worklog| u64::from(worklog.minutes))
+        .sum()
+}
+
+/// Logged time per epic by epic id, followed by the backlog; epics and the
+/// backlog without any logged time are left out.
+pub fn time_by_epic(db_state: &DBState) -> Vec<EpicTime> {
+    let mut epic_ids: Vec<&u32> = db_state
+        .epics
+        .keys()
+        .collect();
+    epic_ids.sort_unstable();
+    let mut rows: Vec<EpicTime> = epic_ids
+        .into_iter()
+        .map(|id| {
+            let epic = &db_state.epics[id];
+            EpicTime {
+                epic_id: Some(*id),
+                name: epic
+                    .name
+                    .clone(),
+                minutes: epic
+                    .stories
+                    .iter()
+                    .filter_map(|id| {
+                        db_state
+                            .stories
+                            .get(id)
+                    })
+                    .map(logged_minutes)
+                    .sum(),
+            }
+        })
+        .collect();
+    rows.push(EpicTime {
+        epic_id: None,
+        name: "backlog".to_owned(),
+        minutes: db_state
+            .backlog()
+            .iter()
+            .map(|id| logged_minutes(&db_state.stories[id]))
+            .sum(),
+    });
+    rows.retain(|row| row.minutes > 0);
+    rows
+}
+
+/// Logged time per user, by name.
+pub fn time_by_user(db_state: &DBState) -> Vec<UserTime> {
+    let mut minutes: BTreeMap<&str, u64> = BTreeMap::new();
+    for worklog in db_state
+        .stories
+        .values()
+        .flat_map(|story| &story.worklogs)
+    {
+        *minutes
+            .entry(&worklog.user)
+            .or_default() += u64::from(worklog.minutes);
+    }
+    minutes
+        .into_iter()
+        .map(|(user, minutes)| UserTime {
+            user: user.to_owned(),
+            minutes,
+        })
+        .collect()
+}
+
+/// Minutes as hours and minutes, e.g. `2h 05m`.
+pub fn format_minutes(minutes: u64) -> String {
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Epic, Worklog};
+    use chrono::Utc;
+
+    fn story(logs: &[(&str, u32)]) -> Story {
+        let mut story = Story::new("".to_owned(), "".to_owned());
+        story.worklogs = logs
+            .iter()
+            .map(|(user, minutes)| Worklog {
+                user: (*user).to_owned(),
+                start: Utc::now(),
+                minutes: *minutes,
+                note: "".to_owned(),
+            })
+            .collect();
+        story
+    }
+
+    #[test]
+    fn reports_should_sum_worklogs_per_epic_and_user() {
+        let mut db_state = DBState::default();
+        let mut epic = Epic::new("client a".to_owned(), "".to_owned());
+        epic.stories = vec![3, 4];
+        db_state
+            .epics
+            .insert(1, epic);
+        db_state
+            .epics
+            .insert(2, Epic::new("idle".to_owned(), "".to_owned()));
+        db_state
+            .stories
+            .insert(3, story(&[("bob", 30), ("alice", 45)]));
+        db_state
+            .stories
+            .insert(4, story(&[("alice", 15)]));
+        db_state
+            .stories
+            .insert(5, story(&[("bob", 10)]));
+
+        assert_eq!(
+            time_by_epic(&db_state),
+            [
+                EpicTime {
+                    epic_id: Some(1),
+                    name: "client a".to_owned(),
+                    minutes: 90,
+                },
+                EpicTime {
+                    epic_id: None,
+                    name: "backlog".to_owned(),
+                    minutes: 10,
+                },
+            ]
+        );
+        assert_eq!(
+            time_by_user(&db_state),
+            [
+                UserTime {
+                    user: "alice".to_owned(),
+                    minutes: 60,
+                },
+                UserTime {
+                    user: "bob".to_owned(),
+                    minutes: 40,
+                },
+            ]
+        );
+        assert_eq!(format_minutes(125), "2h 05m");
+    }
+
+    #[test]
+    fn reports_should_sum_huge_worklogs_without_overflowing() {
+        let mut db_state = DBState::default();
+        db_state
+            .stories
+            .insert(1, story(&[("bob", u32::MAX), ("bob", u32::MAX)]));
+
+        let total = 2 * u64::from(u32::MAX);
+        assert_eq!(time_by_epic(&db_state)[0].minutes, total);
+        assert_eq!(time_by_user(&db_state)[0].minutes, total);
+    }
+}
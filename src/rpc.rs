@@ -0,0 +1,572 @@
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+use itertools::Itertools;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+
+use crate::db::JiraHandle;
+use crate::error::Error;
+use crate::models::{Epic, Status, Story};
+
+// messages are newline delimited json, one request or response per line, so
+// editors can drive the process with a plain line based job/pipe api
+pub fn run(db: &JiraHandle, input: impl BufRead, mut output: impl Write) -> Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line
+            .trim()
+            .is_empty()
+        {
+            continue;
+        }
+
+        for message in handle_message(db, &line) {
+            writeln!(output, "{}", message)?;
+        }
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+const NOT_FOUND: i64 = -32001;
+const CONFLICT: i64 = -32002;
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default, deserialize_with = "present_id")]
+    id: Option<Value>,
+}
+
+// only a missing id makes a request a notification; `"id": null` still gets
+// a response
+fn present_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+// sent after every successful mutation so sidebars can refresh
+#[derive(Serialize, Debug, PartialEq)]
+struct Change {
+    kind: &'static str,
+    id: u32,
+    change: &'static str,
+}
+
+// a new epic or backlog story
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewItemParams {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateStoryParams {
+    epic_id: u32,
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EpicParams {
+    epic_id: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoryParams {
+    story_id: u32,
+}
+
+// the epic is optional so backlog stories can be deleted too
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeleteStoryParams {
+    epic_id: Option<u32>,
+    story_id: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateEpicStatusParams {
+    epic_id: u32,
+    status: Status,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateStoryStatusParams {
+    story_id: u32,
+    status: Status,
+}
+
+// returns the response (if the message was not a notification) followed by
+// any change notifications it triggered
+pub fn handle_message(db: &JiraHandle, message: &str) -> Vec<Value> {
+    let request: Value = match serde_json::from_str(message) {
+        Ok(request) => request,
+        Err(error) => {
+            return vec![error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, error.to_string()),
+            )]
+        }
+    };
+    let id = request
+        .get("id")
+        .cloned()
+        .unwrap_or(Value::Null);
+    let request = match serde_json::from_value::<Request>(request) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        _ => {
+            return vec![error_response(
+                id,
+                RpcError::new(INVALID_REQUEST, "invalid JSON-RPC 2.0 request"),
+            )]
+        }
+    };
+
+    let mut messages = vec![];
+    match dispatch(db, &request.method, request.params) {
+        Ok((result, changes)) => {
+            if let Some(id) = request.id {
+                messages.push(json!({ "jsonrpc": "2.0", "result": result, "id": id }));
+            }
+            for change in changes {
+                messages
+                    .push(json!({ "jsonrpc": "2.0", "method": "boardChanged", "params": change }));
+            }
+        }
+        Err(error) => {
+            if let Some(id) = request.id {
+                messages.push(error_response(id, error));
+            }
+        }
+    }
+    messages
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": error.code, "message": error.message },
+        "id": id,
+    })
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|error| RpcError::new(INVALID_PARAMS, error.to_string()))
+}
+
+fn with_id(id: u32, item: &impl Serialize) -> Value {
+    let mut value = json!(item);
+    if let Some(object) = value.as_object_mut() {
+        object.insert("id".to_owned(), json!(id));
+    }
+    value
+}
+
+fn server_error(error: Error) -> RpcError {
+    let code = match error {
+        Error::NotFound { .. } | Error::ProjectNotFound(_) => NOT_FOUND,
+        Error::Conflict(_) => CONFLICT,
+        Error::Validation(_) => INVALID_PARAMS,
+        Error::Io(_) | Error::Corrupt(_) => SERVER_ERROR,
+    };
+    RpcError::new(code, error.to_string())
+}
+
+fn dispatch(
+    db: &JiraHandle,
+    method: &str,
+    params: Value,
+) -> Result<(Value, Vec<Change>), RpcError> {
+    match method {
+        "listEpics" => {
+            let db_state = db
+                .read_full_record()
+                .map_err(server_error)?;
+            let epics: Vec<Value> = db_state
+                .epics
+                .iter()
+                .sorted_by_key(|(id, _)| **id)
+                .map(|(id, epic)| with_id(*id, epic))
+                .collect();
+            Ok((json!(epics), vec![]))
+        }
+        "listStories" => {
+            let params: EpicParams = parse_params(params)?;
+            let db_state = db
+                .read_full_record()
+                .map_err(server_error)?;
+            let epic = db_state
+                .epics
+                .get(&params.epic_id)
+                .ok_or_else(|| server_error(Error::epic_not_found(params.epic_id)))?;
+            let stories: Vec<Value> = epic
+                .stories
+                .iter()
+                .sorted()
+                .filter_map(|id| {
+                    db_state
+                        .stories
+                        .get(id)
+                        .map(|story| with_id(*id, story))
+                })
+                .collect();
+            Ok((json!(stories), vec![]))
+        }
+        "listBacklog" => {
+            let db_state = db
+                .read_full_record()
+                .map_err(server_error)?;
+            let stories: Vec<Value> = db_state
+                .backlog()
+                .into_iter()
+                .map(|id| with_id(id, &db_state.stories[&id]))
+                .collect();
+            Ok((json!(stories), vec![]))
+        }
+        "getEpic" => {
+            let params: EpicParams = parse_params(params)?;
+            let db_state = db
+                .read_full_record()
+                .map_err(server_error)?;
+            let epic = db_state
+                .epics
+                .get(&params.epic_id)
+                .ok_or_else(|| server_error(Error::epic_not_found(params.epic_id)))?;
+            Ok((with_id(params.epic_id, epic), vec![]))
+        }
+        "getStory" => {
+            let params: StoryParams = parse_params(params)?;
+            let db_state = db
+                .read_full_record()
+                .map_err(server_error)?;
+            let story = db_state
+                .stories
+                .get(&params.story_id)
+                .ok_or_else(|| server_error(Error::story_not_found(params.story_id)))?;
+            Ok((with_id(params.story_id, story), vec![]))
+        }
+        "createEpic" => {
+            let params: NewItemParams = parse_params(params)?;
+            let epic_id = db
+                .create_epic(Epic::new(params.name, params.description))
+                .map_err(server_error)?;
+            Ok((
+                json!({ "id": epic_id }),
+                vec![Change {
+                    kind: "epic",
+                    id: epic_id,
+                    change: "created",
+                }],
+            ))
+        }
+        "createStory" => {
+            let params: CreateStoryParams = parse_params(params)?;
+            let story_id = db
+                .create_story(Story::new(params.name, params.description), params.epic_id)
+                .map_err(server_error)?;
+            Ok((
+                json!({ "id": story_id }),
+                vec![Change {
+                    kind: "story",
+                    id: story_id,
+                    change: "created",
+                }],
+            ))
+        }
+        "createBacklogStory" => {
+            let params: NewItemParams = parse_params(params)?;
+            let story_id = db
+                .create_backlog_story(Story::new(params.name, params.description))
+                .map_err(server_error)?;
+            Ok((
+                json!({ "id": story_id }),
+                vec![Change {
+                    kind: "story",
+                    id: story_id,
+                    change: "created",
+                }],
+            ))
+        }
+        "updateEpicStatus" => {
+            let params: UpdateEpicStatusParams = parse_params(params)?;
+            db.update_epic_status(params.epic_id, params.status)
+                .map_err(server_error)?;
+            Ok((
+                Value::Null,
+                vec![Change {
+                    kind: "epic",
+                    id: params.epic_id,
+                    change: "updated",
+                }],
+            ))
+        }
+        "updateStoryStatus" => {
+            let params: UpdateStoryStatusParams = parse_params(params)?;
+            db.update_story_status(params.story_id, params.status)
+                .map_err(server_error)?;
+            Ok((
+                Value::Null,
+                vec![Change {
+                    kind: "story",
+                    id: params.story_id,
+                    change: "updated",
+                }],
+            ))
+        }
+        "deleteEpic" => {
+            let params: EpicParams = parse_params(params)?;
+            db.delete_epic(params.epic_id)
+                .map_err(server_error)?;
+            Ok((
+                Value::Null,
+                vec![Change {
+                    kind: "epic",
+                    id: params.epic_id,
+                    change: "deleted",
+                }],
+            ))
+        }
+        "deleteStory" => {
+            let params: DeleteStoryParams = parse_params(params)?;
+            let epic_id = match params.epic_id {
+                Some(epic_id) => Some(epic_id),
+                None => db
+                    .read_full_record()
+                    .map_err(server_error)?
+                    .epic_of_story(params.story_id),
+            };
+            match epic_id {
+                Some(epic_id) => db.delete_story(epic_id, params.story_id),
+                None => db.delete_backlog_story(params.story_id),
+            }
+            .map_err(server_error)?;
+            Ok((
+                Value::Null,
+                vec![Change {
+                    kind: "story",
+                    id: params.story_id,
+                    change: "deleted",
+                }],
+            ))
+        }
+        method => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("method not found: {method}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+
+    fn setup_db() -> JiraHandle {
+        JiraHandle {
+            database: Box::new(MockDB::new()),
+        }
+    }
+
+    #[test]
+    fn handle_message_should_respond_and_notify_on_create() {
+        let db = setup_db();
+
+        let messages = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createEpic", "params": { "name": "epic" }, "id": 7 }"#,
+        );
+
+        assert_eq!(
+            messages,
+            vec![
+                json!({ "jsonrpc": "2.0", "result": { "id": 1 }, "id": 7 }),
+                json!({ "jsonrpc": "2.0", "method": "boardChanged", "params": { "kind": "epic", "id": 1, "change": "created" } }),
+            ]
+        );
+    }
+
+    #[test]
+    fn handle_message_should_list_stories_and_update_status() {
+        let db = setup_db();
+        handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createEpic", "params": { "name": "epic" }, "id": 1 }"#,
+        );
+        handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createStory", "params": { "epicId": 1, "name": "story" }, "id": 2 }"#,
+        );
+
+        let messages = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "updateStoryStatus", "params": { "storyId": 2, "status": "Resolved" }, "id": 3 }"#,
+        );
+        assert_eq!(messages.len(), 2);
+
+        let messages = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "listStories", "params": { "epicId": 1 }, "id": 4 }"#,
+        );
+        assert_eq!(messages[0]["result"][0]["id"], 2);
+        assert_eq!(messages[0]["result"][0]["status"], "Resolved");
+    }
+
+    #[test]
+    fn handle_message_should_create_list_and_delete_backlog_stories() {
+        let db = setup_db();
+        handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createEpic", "params": { "name": "epic" }, "id": 1 }"#,
+        );
+        handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createStory", "params": { "epicId": 1, "name": "story" }, "id": 2 }"#,
+        );
+
+        let created = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createBacklogStory", "params": { "name": "chore" }, "id": 3 }"#,
+        );
+        assert_eq!(created[0]["result"]["id"], 3);
+
+        let listed = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "listBacklog", "id": 4 }"#,
+        );
+        assert_eq!(
+            listed[0]["result"],
+            json!([with_id(
+                3,
+                &db.read_full_record()
+                    .unwrap()
+                    .stories[&3]
+            )])
+        );
+
+        // without an epic id the story is deleted wherever it lives
+        for story_id in [3, 2] {
+            let deleted = handle_message(
+                &db,
+                &format!(
+                    r#"{{ "jsonrpc": "2.0", "method": "deleteStory", "params": {{ "storyId": {story_id} }}, "id": 5 }}"#
+                ),
+            );
+            assert_eq!(deleted[0]["result"], Value::Null);
+        }
+        assert!(db
+            .read_full_record()
+            .unwrap()
+            .stories
+            .is_empty());
+    }
+
+    #[test]
+    fn handle_message_should_not_respond_to_notifications() {
+        let db = setup_db();
+
+        let messages = handle_message(&db, r#"{ "jsonrpc": "2.0", "method": "listEpics" }"#);
+
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn handle_message_should_respond_to_null_ids() {
+        let db = setup_db();
+
+        let messages = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "listEpics", "id": null }"#,
+        );
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["id"], Value::Null);
+        assert_eq!(messages[0]["result"], json!([]));
+    }
+
+    #[test]
+    fn handle_message_should_report_protocol_errors() {
+        let db = setup_db();
+
+        let parse_error = handle_message(&db, "{ not json");
+        assert_eq!(parse_error[0]["error"]["code"], PARSE_ERROR);
+
+        let invalid_request = handle_message(
+            &db,
+            r#"{ "jsonrpc": "1.0", "method": "listEpics", "id": 1 }"#,
+        );
+        assert_eq!(invalid_request[0]["error"]["code"], INVALID_REQUEST);
+        assert_eq!(invalid_request[0]["id"], 1);
+
+        let unknown_method =
+            handle_message(&db, r#"{ "jsonrpc": "2.0", "method": "nope", "id": 2 }"#);
+        assert_eq!(unknown_method[0]["error"]["code"], METHOD_NOT_FOUND);
+
+        let invalid_params = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createStory", "params": { "name": "story" }, "id": 3 }"#,
+        );
+        assert_eq!(invalid_params[0]["error"]["code"], INVALID_PARAMS);
+
+        let missing_epic = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "getEpic", "params": { "epicId": 9 }, "id": 4 }"#,
+        );
+        assert_eq!(missing_epic[0]["error"]["code"], NOT_FOUND);
+
+        let empty_name = handle_message(
+            &db,
+            r#"{ "jsonrpc": "2.0", "method": "createEpic", "params": { "name": "" }, "id": 5 }"#,
+        );
+        assert_eq!(empty_name[0]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(empty_name.len(), 1);
+    }
+
+    #[test]
+    fn run_should_write_one_message_per_line() {
+        let db = setup_db();
+        let input = "{ \"jsonrpc\": \"2.0\", \"method\": \"createEpic\", \"params\": { \"name\": \"epic\" }, \"id\": 1 }\n\n{ \"jsonrpc\": \"2.0\", \"method\": \"listEpics\", \"id\": 2 }\n";
+        let mut output = vec![];
+
+        run(&db, input.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output
+            .lines()
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].contains(r#""name":"epic""#));
+    }
+}
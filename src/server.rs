@@ -0,0 +1,517 @@
+use anyhow::anyhow;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::db::JiraHandle;
+use crate::error::{Error, Result};
+use crate::models::{Epic, Status, Story};
+
+// the server is single threaded on purpose: JiraHandle reads and writes the
+// whole database file per call, so serving one request at a time keeps
+// writes from interleaving
+pub fn serve(db: &JiraHandle, addr: &str) -> anyhow::Result<()> {
+    let server = Server::http(addr).map_err(|error| anyhow!("could not bind {addr}: {error}"))?;
+    println!("Serving the board on http://{}", server.server_addr());
+
+    serve_each(server.incoming_requests(), |request| {
+        handle_request(db, request)
+    });
+
+    Ok(())
+}
+
+// a request that cannot be answered, e.g. because the client hung up, is
+// logged and the server keeps going
+fn serve_each<T>(
+    requests: impl Iterator<Item = T>,
+    mut handle: impl FnMut(T) -> anyhow::Result<()>,
+) {
+    for request in requests {
+        if let Err(error) = handle(request) {
+            eprintln!("Error answering request: {error}");
+        }
+    }
+}
+
+pub fn handle_request(db: &JiraHandle, mut request: Request) -> anyhow::Result<()> {
+    let mut body = String::new();
+    let response = match request
+        .as_reader()
+        .read_to_string(&mut body)
+    {
+        Ok(_) => route(db, request.method(), request.url(), &body),
+        Err(error) => ApiResponse::error(400, &format!("could not read request body: {error}")),
+    };
+
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+    request.respond(
+        Response::from_string(
+            response
+                .body
+                .to_string(),
+        )
+        .with_status_code(response.status)
+        .with_header(content_type),
+    )?;
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    fn ok(body: Value) -> Self {
+        ApiResponse { status: 200, body }
+    }
+
+    fn created(id: u32) -> Self {
+        ApiResponse {
+            status: 201,
+            body: json!({ "id": id }),
+        }
+    }
+
+    fn no_content() -> Self {
+        ApiResponse {
+            status: 204,
+            body: Value::Null,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        ApiResponse {
+            status,
+            body: json!({ "error": message }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WithId<'a, T> {
+    id: u32,
+    #[serde(flatten)]
+    item: &'a T,
+}
+
+#[derive(Deserialize)]
+struct NewItem {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct ItemPatch {
+    name: Option<String>,
+    description: Option<String>,
+    status: Option<Status>,
+}
+
+pub fn route(db: &JiraHandle, method: &Method, url: &str, body: &str) -> ApiResponse {
+    let path = url
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    let result = match (method, segments.as_slice()) {
+        (Method::Get, ["epics"]) => list_epics(db),
+        (Method::Post, ["epics"]) => create_epic(db, body),
+        (Method::Get, ["epics", id]) => parse_id(id).and_then(|id| get_epic(db, id)),
+        (Method::Patch, ["epics", id]) => parse_id(id).and_then(|id| patch_epic(db, id, body)),
+        (Method::Delete, ["epics", id]) => parse_id(id).and_then(|id| delete_epic(db, id)),
+        (Method::Get, ["epics", id, "stories"]) => parse_id(id).and_then(|id| list_stories(db, id)),
+        (Method::Post, ["epics", id, "stories"]) => {
+            parse_id(id).and_then(|id| create_story(db, id, body))
+        }
+        (Method::Get, ["backlog"]) => list_backlog(db),
+        (Method::Post, ["backlog"]) => create_backlog_story(db, body),
+        (Method::Get, ["stories", id]) => parse_id(id).and_then(|id| get_story(db, id)),
+        (Method::Patch, ["stories", id]) => parse_id(id).and_then(|id| patch_story(db, id, body)),
+        (Method::Delete, ["stories", id]) => parse_id(id).and_then(|id| delete_story(db, id)),
+        _ => Ok(ApiResponse::error(
+            404,
+            &format!("no route for {method} {path}"),
+        )),
+    };
+
+    result.unwrap_or_else(|error| ApiResponse::error(error_status(&error), &error.to_string()))
+}
+
+fn error_status(error: &Error) -> u16 {
+    match error {
+        Error::NotFound { .. } | Error::ProjectNotFound(_) => 404,
+        Error::Validation(_) => 400,
+        Error::Conflict(_) => 409,
+        Error::Io(_) | Error::Corrupt(_) => 500,
+    }
+}
+
+fn parse_id(id: &str) -> Result<u32> {
+    id.parse::<u32>()
+        .map_err(|_| Error::Validation(format!("invalid id: {id}")))
+}
+
+fn parse_body<'a, T: Deserialize<'a>>(body: &'a str) -> Result<T> {
+    serde_json::from_str(body)
+        .map_err(|error| Error::Validation(format!("invalid request body: {error}")))
+}
+
+fn list_epics(db: &JiraHandle) -> Result<ApiResponse> {
+    let db_state = db.read_full_record()?;
+    let epics: Vec<WithId<Epic>> = db_state
+        .epics
+        .iter()
+        .sorted_by_key(|(id, _)| **id)
+        .map(|(id, epic)| WithId {
+            id: *id,
+            item: epic,
+        })
+        .collect();
+    Ok(ApiResponse::ok(json!(epics)))
+}
+
+fn create_epic(db: &JiraHandle, body: &str) -> Result<ApiResponse> {
+    let new_epic: NewItem = parse_body(body)?;
+    let epic_id = db.create_epic(Epic::new(new_epic.name, new_epic.description))?;
+    Ok(ApiResponse::created(epic_id))
+}
+
+fn get_epic(db: &JiraHandle, epic_id: u32) -> Result<ApiResponse> {
+    let db_state = db.read_full_record()?;
+    let epic = db_state
+        .epics
+        .get(&epic_id)
+        .ok_or_else(|| Error::epic_not_found(epic_id))?;
+    Ok(ApiResponse::ok(json!(WithId {
+        id: epic_id,
+        item: epic
+    })))
+}
+
+fn patch_epic(db: &JiraHandle, epic_id: u32, body: &str) -> Result<ApiResponse> {
+    let patch: ItemPatch = parse_body(body)?;
+    db.update_epic_details(epic_id, patch.name, patch.description, patch.status)?;
+    get_epic(db, epic_id)
+}
+
+fn delete_epic(db: &JiraHandle, epic_id: u32) -> Result<ApiResponse> {
+    db.delete_epic(epic_id)?;
+    Ok(ApiResponse::no_content())
+}
+
+fn list_stories(db: &JiraHandle, epic_id: u32) -> Result<ApiResponse> {
+    let db_state = db.read_full_record()?;
+    let epic = db_state
+        .epics
+        .get(&epic_id)
+        .ok_or_else(|| Error::epic_not_found(epic_id))?;
+    let stories: Vec<WithId<Story>> = epic
+        .stories
+        .iter()
+        .sorted()
+        .filter_map(|id| {
+            db_state
+                .stories
+                .get(id)
+                .map(|story| WithId {
+                    id: *id,
+                    item: story,
+                })
+        })
+        .collect();
+    Ok(ApiResponse::ok(json!(stories)))
+}
+
+fn create_story(db: &JiraHandle, epic_id: u32, body: &str) -> Result<ApiResponse> {
+    let new_story: NewItem = parse_body(body)?;
+    let story_id = db.create_story(Story::new(new_story.name, new_story.description), epic_id)?;
+    Ok(ApiResponse::created(story_id))
+}
+
+fn list_backlog(db: &JiraHandle) -> Result<ApiResponse> {
+    let db_state = db.read_full_record()?;
+    let stories: Vec<WithId<Story>> = db_state
+        .backlog()
+        .into_iter()
+        .map(|id| WithId {
+            id,
+            item: &db_state.stories[&id],
+        })
+        .collect();
+    Ok(ApiResponse::ok(json!(stories)))
+}
+
+fn create_backlog_story(db: &JiraHandle, body: &str) -> Result<ApiResponse> {
+    let new_story: NewItem = parse_body(body)?;
+    let story_id = db.create_backlog_story(Story::new(new_story.name, new_story.description))?;
+    Ok(ApiResponse::created(story_id))
+}
+
+fn get_story(db: &JiraHandle, story_id: u32) -> Result<ApiResponse> {
+    let db_state = db.read_full_record()?;
+    let story = db_state
+        .stories
+        .get(&story_id)
+        .ok_or_else(|| Error::story_not_found(story_id))?;
+    Ok(ApiResponse::ok(json!(WithId {
+        id: story_id,
+        item: story
+    })))
+}
+
+fn patch_story(db: &JiraHandle, story_id: u32, body: &str) -> Result<ApiResponse> {
+    let patch: ItemPatch = parse_body(body)?;
+    db.update_story_details(story_id, patch.name, patch.description, patch.status)?;
+    get_story(db, story_id)
+}
+
+fn delete_story(db: &JiraHandle, story_id: u32) -> Result<ApiResponse> {
+    let db_state = db.read_full_record()?;
+    match db_state.epic_of_story(story_id) {
+        Some(epic_id) => db.delete_story(epic_id, story_id)?,
+        None => db.delete_backlog_story(story_id)?,
+    }
+    Ok(ApiResponse::no_content())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    fn setup_db() -> JiraHandle {
+        JiraHandle {
+            database: Box::new(MockDB::new()),
+        }
+    }
+
+    #[test]
+    fn route_should_create_and_list_epics() {
+        let db = setup_db();
+
+        let created = route(
+            &db,
+            &Method::Post,
+            "/epics",
+            r#"{ "name": "epic", "description": "desc" }"#,
+        );
+        assert_eq!(created, ApiResponse::created(1));
+
+        let listed = route(&db, &Method::Get, "/epics", "");
+        assert_eq!(listed.status, 200);
+        assert_eq!(listed.body[0]["id"], 1);
+        assert_eq!(listed.body[0]["name"], "epic");
+        assert_eq!(listed.body[0]["status"], "Open");
+    }
+
+    #[test]
+    fn route_should_create_patch_and_delete_stories() {
+        let db = setup_db();
+        route(&db, &Method::Post, "/epics", r#"{ "name": "epic" }"#);
+
+        let created = route(
+            &db,
+            &Method::Post,
+            "/epics/1/stories",
+            r#"{ "name": "story" }"#,
+        );
+        assert_eq!(created, ApiResponse::created(2));
+
+        let patched = route(
+            &db,
+            &Method::Patch,
+            "/stories/2",
+            r#"{ "status": "InProgress", "description": "updated" }"#,
+        );
+        assert_eq!(patched.status, 200);
+        assert_eq!(patched.body["status"], "InProgress");
+        assert_eq!(patched.body["description"], "updated");
+        assert_eq!(patched.body["name"], "story");
+
+        let listed = route(&db, &Method::Get, "/epics/1/stories", "");
+        assert_eq!(
+            listed
+                .body
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert_eq!(
+            route(&db, &Method::Delete, "/stories/2", ""),
+            ApiResponse::no_content()
+        );
+        assert_eq!(route(&db, &Method::Get, "/stories/2", "").status, 404);
+    }
+
+    #[test]
+    fn route_should_create_list_and_delete_backlog_stories() {
+        let db = setup_db();
+        route(&db, &Method::Post, "/epics", r#"{ "name": "epic" }"#);
+        route(
+            &db,
+            &Method::Post,
+            "/epics/1/stories",
+            r#"{ "name": "story" }"#,
+        );
+
+        let created = route(&db, &Method::Post, "/backlog", r#"{ "name": "chore" }"#);
+        assert_eq!(created, ApiResponse::created(3));
+
+        let listed = route(&db, &Method::Get, "/backlog", "");
+        assert_eq!(listed.status, 200);
+        assert_eq!(listed.body[0]["id"], 3);
+        assert_eq!(listed.body[0]["name"], "chore");
+        assert_eq!(
+            listed
+                .body
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert_eq!(
+            route(&db, &Method::Delete, "/stories/3", ""),
+            ApiResponse::no_content()
+        );
+        assert_eq!(route(&db, &Method::Get, "/stories/3", "").status, 404);
+    }
+
+    #[test]
+    fn route_should_reject_unknown_ids_routes_and_bodies() {
+        let db = setup_db();
+        route(&db, &Method::Post, "/epics", r#"{ "name": "epic" }"#);
+
+        assert_eq!(route(&db, &Method::Get, "/epics/999", "").status, 404);
+        assert_eq!(route(&db, &Method::Delete, "/epics/999", "").status, 404);
+        assert_eq!(
+            route(
+                &db,
+                &Method::Post,
+                "/epics/999/stories",
+                r#"{ "name": "story" }"#
+            )
+            .status,
+            404
+        );
+        assert_eq!(route(&db, &Method::Get, "/epics/abc", "").status, 400);
+        assert_eq!(route(&db, &Method::Post, "/epics", "not json").status, 400);
+        assert_eq!(route(&db, &Method::Get, "/unknown", "").status, 404);
+        assert_eq!(
+            route(&db, &Method::Patch, "/epics/1", r#"{ "name": "" }"#).status,
+            400
+        );
+        assert_eq!(
+            route(&db, &Method::Post, "/epics", r#"{ "name": " " }"#).status,
+            400
+        );
+        assert_eq!(
+            route(&db, &Method::Post, "/backlog", r#"{ "name": "" }"#).status,
+            400
+        );
+    }
+
+    #[test]
+    fn route_should_apply_a_patch_whole_or_not_at_all() {
+        let db = setup_db();
+        route(&db, &Method::Post, "/epics", r#"{ "name": "epic" }"#);
+        let before = db
+            .read_full_record()
+            .unwrap();
+
+        assert_eq!(
+            route(
+                &db,
+                &Method::Patch,
+                "/stories/999",
+                r#"{ "status": "Closed" }"#
+            )
+            .status,
+            404
+        );
+        assert_eq!(
+            route(
+                &db,
+                &Method::Patch,
+                "/epics/1",
+                r#"{ "name": " ", "status": "Closed" }"#
+            )
+            .status,
+            400
+        );
+        assert_eq!(
+            db.read_full_record()
+                .unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn server_should_answer_over_localhost() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server
+            .server_addr()
+            .to_ip()
+            .unwrap()
+            .port();
+
+        let handle = thread::spawn(move || {
+            let db = setup_db();
+            let request = server
+                .recv()
+                .unwrap();
+            handle_request(&db, request).unwrap();
+        });
+
+        let body = r#"{ "name": "epic" }"#;
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "POST /epics HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .unwrap();
+        handle
+            .join()
+            .unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 201"));
+        assert!(response.ends_with(r#"{"id":1}"#));
+    }
+
+    #[test]
+    fn serve_each_should_keep_serving_after_a_failed_request() {
+        let mut served = vec![];
+
+        serve_each([1, 2, 3].into_iter(), |request| {
+            served.push(request);
+            match request {
+                1 => Err(anyhow!("client hung up")),
+                _ => Ok(()),
+            }
+        });
+
+        assert_eq!(served, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,331 @@
+//! Named templates from the config that pre-fill new epics and stories.
+//!
+//! An epic template may also list the stories created together with the
+//! epic:
+//!
+//! ```json
+//! { "templates": [
+//!     { "name": "bug report", "type": "bug", "priority": "high",
+//!       "description": "## Expected\n\n## Actual\n", "labels": ["triage"] },
+//!     { "name": "release epic", "kind": "epic",
+//!       "stories": [{ "name": "Changelog" }, { "name": "Tag release", "type": "task" }] }
+//! ] }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+
+use chrono::NaiveDate;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+
+use crate::fields::FieldDef;
+use crate::models::{BugDetails, IssueType, Priority, Story};
+
+/// What a template creates.
+#[derive(Deserialize, Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateKind {
+    Epic,
+    #[default]
+    Story,
+}
+
+/// A template from the config; every value but the name is optional.
+#[derive(Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Template {
+    pub name: String,
+    pub kind: TemplateKind,
+    pub description: String,
+    #[serde(deserialize_with = "priority")]
+    pub priority: Priority,
+    /// Issue type of a story template.
+    #[serde(rename = "type", deserialize_with = "issue_type")]
+    pub issue_type: IssueType,
+    pub labels: Vec<String>,
+    /// Custom field values, written as they would be entered.
+    pub fields: BTreeMap<String, String>,
+    /// Stories created with an epic template; their `name` is the story
+    /// name.
+    pub stories: Vec<Template>,
+}
+
+impl Template {
+    /// The template fields as stored values; dates such as `+1w` are
+    /// relative to `today`.
+    pub fn field_values(&self, defs: &[FieldDef], today: NaiveDate) -> BTreeMap<String, String> {
+        self.fields
+            .iter()
+            .filter_map(|(name, value)| {
+                let def = defs
+                    .iter()
+                    .find(|def| {
+                        def.name
+                            .eq_ignore_ascii_case(name)
+                    })?;
+                let value = def
+                    .parse(value, today)
+                    .ok()??;
+                Some((
+                    def.name
+                        .clone(),
+                    value,
+                ))
+            })
+            .collect()
+    }
+
+    /// Story filled in from this template, used for the stories of an epic
+    /// template.
+    pub fn story(&self, defs: &[FieldDef], today: NaiveDate) -> Story {
+        let mut story = match self.issue_type {
+            IssueType::Bug => Story::new_bug(
+                self.name
+                    .clone(),
+                self.description
+                    .clone(),
+                BugDetails::default(),
+            ),
+            issue_type => Story {
+                issue_type,
+                ..Story::new(
+                    self.name
+                        .clone(),
+                    self.description
+                        .clone(),
+                )
+            },
+        };
+        story.priority = self.priority;
+        story.labels = self
+            .labels
+            .clone();
+        story.fields = self.field_values(defs, today);
+        story
+    }
+}
+
+/// Templates that create `kind`, in config order.
+pub fn templates_of(templates: &[Template], kind: TemplateKind) -> Vec<&Template> {
+    templates
+        .iter()
+        .filter(|template| template.kind == kind)
+        .collect()
+}
+
+/// Checks that template names are unique per kind, that only epic templates list
+/// stories and that field values fit the field definitions.
+pub fn validate_templates(
+    templates: &[Template],
+    defs: &[FieldDef],
+    today: NaiveDate,
+) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for template in templates {
+        let name = template
+            .name
+            .trim();
+        if name.is_empty() {
+            return Err("templates need a name".to_owned());
+        }
+        if !names.insert((template.kind, name.to_lowercase())) {
+            return Err(format!("template `{}` is defined twice", name));
+        }
+        if template.kind == TemplateKind::Story
+            && !template
+                .stories
+                .is_empty()
+        {
+            return Err(format!("only epic templates take stories, not `{}`", name));
+        }
+        validate_fields(template, defs, today)?;
+        for story in template
+            .stories
+            .iter()
+        {
+            if story
+                .name
+                .trim()
+                .is_empty()
+            {
+                return Err(format!("stories of template `{}` need a name", name));
+            }
+            if story.kind != TemplateKind::Story
+                || !story
+                    .stories
+                    .is_empty()
+            {
+                return Err(format!(
+                    "stories of template `{}` cannot have stories of their own",
+                    name
+                ));
+            }
+            validate_fields(story, defs, today)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_fields(template: &Template, defs: &[FieldDef], today: NaiveDate) -> Result<(), String> {
+    for (name, value) in template
+        .fields
+        .iter()
+    {
+        let def = defs
+            .iter()
+            .find(|def| {
+                def.name
+                    .eq_ignore_ascii_case(name)
+            })
+            .ok_or_else(|| format!("template `{}` sets unknown field `{}`", template.name, name))?;
+        def.parse(value, today)
+            .map_err(|error| format!("template `{}`: {}", template.name, error))?;
+    }
+    Ok(())
+}
+
+// priorities and issue types are written in lowercase in the config, as in
+// the editor form
+fn priority<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Priority, D::Error> {
+    choice(deserializer, "priority", &Priority::ALL)
+}
+
+fn issue_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IssueType, D::Error> {
+    choice(deserializer, "type", &IssueType::ALL)
+}
+
+fn choice<'de, D: Deserializer<'de>, T: Display + Copy>(
+    deserializer: D,
+    field: &str,
+    options: &[T],
+) -> Result<T, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    options
+        .iter()
+        .copied()
+        .find(|option| {
+            option
+                .to_string()
+                .eq_ignore_ascii_case(&value)
+        })
+        .ok_or_else(|| D::Error::custom(format!("unknown {} `{}`", field, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::FieldKind;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+    }
+
+    fn defs() -> Vec<FieldDef> {
+        vec![
+            FieldDef::new("customer", FieldKind::Text),
+            FieldDef::new("launch", FieldKind::Date),
+        ]
+    }
+
+    #[test]
+    fn templates_should_deserialize_from_lowercase_names() {
+        let templates: Vec<Template> = serde_json::from_str(
+            r#"[
+                { "name": "bug report", "type": "bug", "priority": "high", "labels": ["triage"] },
+                { "name": "release epic", "kind": "epic", "stories": [{ "name": "Changelog" }] }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(templates[0].issue_type, IssueType::Bug);
+        assert_eq!(templates[0].priority, Priority::High);
+        assert_eq!(
+            templates_of(&templates, TemplateKind::Epic)
+                .into_iter()
+                .map(|template| template
+                    .name
+                    .as_str())
+                .collect::<Vec<_>>(),
+            ["release epic"]
+        );
+        assert!(serde_json::from_str::<Template>(r#"{ "name": "a", "type": "epic" }"#).is_err());
+    }
+
+    #[test]
+    fn story_should_fill_in_template_values() {
+        let template = Template {
+            name: "Tag release".to_owned(),
+            issue_type: IssueType::Bug,
+            labels: vec!["release".to_owned()],
+            fields: BTreeMap::from([("Launch".to_owned(), "+1d".to_owned())]),
+            ..Template::default()
+        };
+
+        let story = template.story(&defs(), today());
+
+        assert_eq!(story.name, "Tag release");
+        assert_eq!(story.issue_type, IssueType::Bug);
+        assert!(story
+            .bug
+            .is_some());
+        assert_eq!(story.labels, ["release"]);
+        assert_eq!(
+            story.fields,
+            BTreeMap::from([("launch".to_owned(), "2026-10-15".to_owned())])
+        );
+    }
+
+    #[test]
+    fn validate_templates_should_reject_invalid_templates() {
+        let story = |name: &str| Template {
+            name: name.to_owned(),
+            ..Template::default()
+        };
+
+        assert_eq!(
+            validate_templates(&[story("spike"), story("Spike")], &defs(), today()),
+            Err("template `Spike` is defined twice".to_owned())
+        );
+        assert_eq!(
+            validate_templates(
+                &[Template {
+                    stories: vec![story("child")],
+                    ..story("spike")
+                }],
+                &defs(),
+                today()
+            ),
+            Err("only epic templates take stories, not `spike`".to_owned())
+        );
+        assert_eq!(
+            validate_templates(
+                &[Template {
+                    kind: TemplateKind::Epic,
+                    stories: vec![Template {
+                        fields: BTreeMap::from([("owner".to_owned(), "me".to_owned())]),
+                        ..story("child")
+                    }],
+                    ..story("release")
+                }],
+                &defs(),
+                today()
+            ),
+            Err("template `child` sets unknown field `owner`".to_owned())
+        );
+        assert_eq!(
+            validate_templates(
+                &[Template {
+                    fields: BTreeMap::from([("launch".to_owned(), "someday".to_owned())]),
+                    ..story("spike")
+                }],
+                &defs(),
+                today()
+            ),
+            Err(
+                "template `spike`: launch: expected date, e.g. 2026-11-01, +3d or friday but found `someday`"
+                    .to_owned()
+            )
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use anyhow::Result;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{
+    self, BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+
+use crate::config::{Config, Palette};
+use crate::db::JiraHandle;
+use crate::io_utils::is_interrupt;
+use crate::navigator::Navigator;
+use crate::ui::{display_width, truncate_to_width, Frame, Page, Span};
+
+const KEY_HINTS: &str = "[↑↓] select | [enter] open | [esc] back | [ctrl-c] quit";
+
+// raw mode and the alternate screen are restored on drop, including when the
+// loop bails out with an error or panics
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Up,
+    Down,
+    Open,
+    Back,
+    Digit(char),
+    Erase,
+    Key(String),
+    Redraw,
+    Quit,
+    Ignore,
+}
+
+fn map_event(event: &Event) -> Command {
+    match event {
+        Event::Key(key) if key.kind == KeyEventKind::Press => map_key(key),
+        Event::Resize(..) => Command::Redraw,
+        _ => Command::Ignore,
+    }
+}
+
+fn map_key(key: &KeyEvent) -> Command {
+    if is_interrupt(key) {
+        return Command::Quit;
+    }
+
+    match key.code {
+        KeyCode::Up => Command::Up,
+        KeyCode::Down => Command::Down,
+        KeyCode::Enter => Command::Open,
+        KeyCode::Esc | KeyCode::Left => Command::Back,
+        KeyCode::Backspace => Command::Erase,
+        KeyCode::Char(c) if c.is_ascii_digit() => Command::Digit(c),
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .contains(KeyModifiers::CONTROL) =>
+        {
+            Command::Key(c.to_string())
+        }
+        _ => Command::Ignore,
+    }
+}
+
+pub fn run(db: Rc<JiraHandle>, config: &Config) -> Result<()> {
+    let palette = config.palette();
+    let _terminal = TerminalGuard::enter()?;
+    let mut stdout = io::stdout();
+    let mut navigator = Navigator::with_config(db, config);
+    let mut message: Option<String> = None;
+    // digits typed so far, so ids can still be entered directly
+    let mut typed_id = String::new();
+
+    loop {
+        let input = {
+            let page = match navigator.get_current_page() {
+                Some(page) => page,
+                None => break,
+            };
+
+            let mut frame = Frame::new(terminal::size()?.0 as usize);
+            let draw_error = page
+                .draw_page(&mut frame)
+                .err()
+                .map(|error| format!("Error rendering page: {}", error));
+            let status = match (&draw_error, &message) {
+                _ if !typed_id.is_empty() => format!("go to: {}", typed_id),
+                (Some(error), _) | (None, Some(error)) => error.clone(),
+                (None, None) => String::new(),
+            };
+            render(&mut stdout, &frame, &palette, &status)?;
+
+            match map_event(&event::read()?) {
+                Command::Up => {
+                    move_selection(page, -1)?;
+                    continue;
+                }
+                Command::Down => {
+                    move_selection(page, 1)?;
+                    continue;
+                }
+                Command::Digit(c) => {
+                    typed_id.push(c);
+                    continue;
+                }
+                Command::Erase if !typed_id.is_empty() => {
+                    typed_id.pop();
+                    continue;
+                }
+                Command::Back if !typed_id.is_empty() => {
+                    typed_id.clear();
+                    continue;
+                }
+                // leaving a page is not left to the pages, where `p` steps
+                // back through result pages
+                Command::Erase | Command::Back => {
+                    message = None;
+                    navigator.go_back();
+                    continue;
+                }
+                Command::Open if !typed_id.is_empty() => std::mem::take(&mut typed_id),
+                Command::Open => match selected_id(page)? {
+                    Some(id) => id.to_string(),
+                    None => continue,
+                },
+                Command::Key(key) => key,
+                Command::Quit => break,
+                Command::Redraw | Command::Ignore => continue,
+            }
+        };
+
+        message = None;
+        let action = navigator
+            .get_current_page()
+            .map(|page| page.handle_input(&input))
+            .transpose();
+        match action {
+            Err(error) => message = Some(format!("Error getting user input: {}", error)),
+            Ok(action) => {
+                if let Some(action) = action.flatten() {
+                    if let Err(error) = navigator.handle_action(action) {
+                        message =
+                            Some(format!("Error handling processing user input: {:#}", error));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn move_selection(page: &dyn Page, delta: isize) -> Result<()> {
+    if let Some(selection) = page.selection() {
+        selection.move_by(
+            delta,
+            page.selectable_ids()?
+                .len(),
+        );
+    }
+    Ok(())
+}
+
+fn selected_id(page: &dyn Page) -> Result<Option<u32>> {
+    Ok(match page.selection() {
+        Some(selection) => selection.selected(&page.selectable_ids()?),
+        None => None,
+    })
+}
+
+fn clip(text: &str, width: usize) -> &str {
+    truncate_to_width(text, width).0
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let (text, used) = truncate_to_width(text, width);
+    format!("{}{}", text, " ".repeat(width - used))
+}
+
+// splits a line into runs of the same color; where spans overlap the one
+// added last wins
+fn color_segments<'a>(
+    line: &'a str,
+    spans: &[Span],
+    palette: &Palette,
+) -> Vec<(&'a str, Option<Color>)> {
+    let mut bounds = vec![0, line.len()];
+    for span in spans {
+        bounds.push(
+            span.range
+                .start
+                .min(line.len()),
+        );
+        bounds.push(
+            span.range
+                .end
+                .min(line.len()),
+        );
+    }
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut segments: Vec<(&str, Option<Color>)> = vec![];
+    for pair in bounds.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let color = spans
+            .iter()
+            .rev()
+            .find(|span| {
+                span.range
+                    .start
+                    <= start
+                    && end
+                        <= span
+                            .range
+                            .end
+            })
+            .and_then(|span| palette.color(span.tone));
+        match segments.last_mut() {
+            Some((text, last_color)) if *last_color == color => {
+                *text = &line[start - text.len()..end];
+            }
+            _ => segments.push((&line[start..end], color)),
+        }
+    }
+    segments
+}
+
+fn render(out: &mut impl Write, frame: &Frame, palette: &Palette, status: &str) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let width = columns as usize;
+    let body_rows = rows.saturating_sub(1) as usize;
+
+    // scroll just enough to keep the highlighted row on screen
+    let offset = match frame.highlighted() {
+        Some(row) if row >= body_rows => row + 1 - body_rows,
+        _ => 0,
+    };
+
+    queue!(out, BeginSynchronizedUpdate)?;
+    for row in 0..body_rows {
+        queue!(out, MoveTo(0, row as u16))?;
+        if let Some(line) = frame
+            .lines()
+            .get(offset + row)
+        {
+            let highlighted = frame.highlighted() == Some(offset + row);
+            if highlighted {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            let line = clip(line, width);
+            for (segment, color) in color_segments(line, frame.spans(offset + row), palette) {
+                match color {
+                    Some(color) => queue!(
+                        out,
+                        SetForegroundColor(color),
+                        Print(segment),
+                        SetForegroundColor(Color::Reset)
+                    )?,
+                    None => queue!(out, Print(segment))?,
+                }
+            }
+            if highlighted {
+                queue!(out, SetAttribute(Attribute::Reset))?;
+            }
+        }
+        queue!(out, Clear(ClearType::UntilNewLine))?;
+    }
+
+    let hints_width = display_width(KEY_HINTS);
+    let status_bar = if display_width(status) + hints_width + 1 > width {
+        pad(status, width)
+    } else {
+        format!("{}{}", pad(status, width - hints_width), KEY_HINTS)
+    };
+    queue!(
+        out,
+        MoveTo(0, rows.saturating_sub(1)),
+        SetAttribute(Attribute::Reverse),
+        Print(status_bar),
+        SetAttribute(Attribute::Reset),
+        EndSynchronizedUpdate
+    )?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ThemeName;
+    use crate::models::Status;
+    use crate::ui::Tone;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn pad_should_fill_display_width() {
+        assert_eq!(pad("日本語", 5), "日本 ");
+        assert_eq!(pad("é", 3), "é  ");
+        assert_eq!(clip("日本語", 5), "日本");
+    }
+
+    #[test]
+    fn color_segments_should_let_later_spans_win() {
+        let palette = ThemeName::Default.palette();
+        let spans = [
+            Span {
+                range: 0..9,
+                tone: Tone::Alert,
+            },
+            Span {
+                range: 5..9,
+                tone: Tone::Status(Status::Open),
+            },
+        ];
+
+        assert_eq!(
+            color_segments("id | OPEN", &spans, &palette),
+            [("id | ", Some(Color::Red)), ("OPEN", Some(Color::Blue))]
+        );
+        assert_eq!(
+            color_segments("id | OPEN", &spans, &Palette::default()),
+            [("id | OPEN", None)]
+        );
+    }
+
+    #[test]
+    fn map_event_should_map_navigation_keys() {
+        assert_eq!(map_event(&key(KeyCode::Up)), Command::Up);
+        assert_eq!(map_event(&key(KeyCode::Down)), Command::Down);
+        assert_eq!(map_event(&key(KeyCode::Enter)), Command::Open);
+        assert_eq!(map_event(&key(KeyCode::Esc)), Command::Back);
+        assert_eq!(map_event(&key(KeyCode::Backspace)), Command::Erase);
+        assert_eq!(map_event(&Event::Resize(80, 24)), Command::Redraw);
+    }
+
+    #[test]
+    fn map_event_should_separate_digits_from_shortcuts() {
+        assert_eq!(map_event(&key(KeyCode::Char('7'))), Command::Digit('7'));
+        assert_eq!(
+            map_event(&key(KeyCode::Char('c'))),
+            Command::Key("c".to_owned())
+        );
+        assert_eq!(
+            map_event(&Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL
+            ))),
+            Command::Quit
+        );
+        assert_eq!(map_event(&key(KeyCode::F(1))), Command::Ignore);
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use crate::dates::today;
+use crate::fields::FieldDef;
+use crate::models::{BugDetails, Epic, IssueType, Priority, Severity, Story};
+use crate::templates::Template;
+
+// the file opened in $EDITOR has a header with one `field: value` per line
+// and everything below the separator is the description, so it can span
+// several paragraphs; stories get a second section for the steps to
+// reproduce a bug
+const SEPARATOR: &str = "---";
+const STEPS_SEPARATOR: &str = "--- steps to reproduce (bugs only)";
+const ERROR_PREFIX: &str = "# error: ";
+
+// epics have no issue type, so their form leaves out the story fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormKind {
+    Epic,
+    Story,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ItemForm {
+    pub name: String,
+    pub priority: Priority,
+    pub description: String,
+    pub issue_type: IssueType,
+    pub severity: Severity,
+    pub steps_to_reproduce: String,
+    pub fields: BTreeMap<String, String>,
+    pub labels: Vec<String>,
+}
+
+impl ItemForm {
+    pub fn new(name: String, description: String) -> Self {
+        ItemForm {
+            name,
+            priority: Priority::default(),
+            description,
+            issue_type: IssueType::default(),
+            severity: Severity::default(),
+            steps_to_reproduce: String::new(),
+            fields: BTreeMap::new(),
+            labels: vec![],
+        }
+    }
+
+    /// Form pre-filled from a template, with the name left to the user.
+    pub fn from_template(template: &Template, defs: &[FieldDef]) -> Self {
+        ItemForm {
+            priority: template.priority,
+            issue_type: template.issue_type,
+            fields: template.field_values(defs, today()),
+            labels: template
+                .labels
+                .clone(),
+            ..ItemForm::new(
+                String::new(),
+                template
+                    .description
+                    .clone(),
+            )
+        }
+    }
+
+    pub fn into_epic(self) -> Epic {
+        let mut epic = Epic::new(self.name, self.description);
+        epic.priority = self.priority;
+        epic.fields = self.fields;
+        epic.labels = self.labels;
+        epic
+    }
+
+    pub fn into_story(self) -> Story {
+        let mut story = match self.issue_type {
+            IssueType::Bug => Story::new_bug(
+                self.name,
+                self.description,
+                BugDetails {
+                    steps_to_reproduce: self.steps_to_reproduce,
+                    severity: self.severity,
+                },
+            ),
+            issue_type => Story {
+                issue_type,
+                ..Story::new(self.name, self.description)
+            },
+        };
+        story.priority = self.priority;
+        story.fields = self.fields;
+        story.labels = self.labels;
+        story
+    }
+}
+
+fn lowercase(value: impl Display) -> String {
+    value
+        .to_string()
+        .to_lowercase()
+}
+
+// the form with the values of `form` filled in, which are blank unless
+// they come from a template
+pub fn render_form(title: &str, kind: FormKind, defs: &[FieldDef], form: &ItemForm) -> String {
+    let mut text = format!(
+        "# {}: fill in the fields, write the description below the `{}` line, then save and quit.\n\
+         # Lines starting with '#' above the description are ignored. Leave the name and description empty to cancel.\n\
+         name: {}\n\
+         priority: {}\n\
+         # comma separated\n\
+         labels: {}\n",
+        title,
+        SEPARATOR,
+        form.name,
+        lowercase(form.priority),
+        form.labels
+            .join(", ")
+    );
+    if kind == FormKind::Story {
+        text.push_str(&format!(
+            "# {}\n\
+             type: {}\n\
+             # bugs only: {}\n\
+             severity: {}\n",
+            choices(&IssueType::ALL),
+            lowercase(form.issue_type),
+            choices(&Severity::ALL),
+            lowercase(form.severity)
+        ));
+    }
+    for def in defs {
+        let value = form
+            .fields
+            .get(&def.name)
+            .map_or("", String::as_str);
+        text.push_str(&format!("# {}\n{}: {}\n", def.hint(), def.name, value));
+    }
+    text.push_str(SEPARATOR);
+    text.push('\n');
+    if !form
+        .description
+        .is_empty()
+    {
+        text.push_str(&format!("{}\n", form.description));
+    }
+    if kind == FormKind::Story {
+        text.push_str(&format!("\n{}\n", STEPS_SEPARATOR));
+        if !form
+            .steps_to_reproduce
+            .is_empty()
+        {
+            text.push_str(&format!("{}\n", form.steps_to_reproduce));
+        }
+    }
+    text
+}
+
+// puts the error at the top of what the user wrote so they can fix it
+// without losing their text
+pub fn with_error(text: &str, error: &str) -> String {
+    let text: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.starts_with(ERROR_PREFIX))
+        .collect();
+    format!("{}{}\n{}\n", ERROR_PREFIX, error, text.join("\n"))
+}
+
+// "a, b or c" from the lowercase names of the options
+fn choices<T: Display>(options: &[T]) -> String {
+    let names: Vec<String> = options
+        .iter()
+        .map(lowercase)
+        .collect();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => names.join(""),
+    }
+}
+
+fn parse_choice<T: Display + Copy>(field: &str, value: &str, options: &[T]) -> Result<T, String> {
+    options
+        .iter()
+        .copied()
+        .find(|option| {
+            option
+                .to_string()
+                .eq_ignore_ascii_case(value)
+        })
+        .ok_or_else(|| format!("unknown {} `{}`, use {}", field, value, choices(options)))
+}
+
+// None when the form was left empty, which cancels the prompt
+pub fn parse_form(
+    text: &str,
+    kind: FormKind,
+    defs: &[FieldDef],
+) -> Result<Option<ItemForm>, String> {
+    let mut form = ItemForm::new(String::new(), String::new());
+    let mut lines = text.lines();
+
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line == SEPARATOR {
+            break;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (field, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("expected `field: value` but found `{}`", line))?;
+        let value = value.trim();
+        match (
+            field
+                .trim()
+                .to_lowercase()
+                .as_str(),
+            kind,
+        ) {
+            ("name", _) => form.name = value.to_owned(),
+            ("priority", _) => form.priority = parse_choice("priority", value, &Priority::ALL)?,
+            ("labels", _) => {
+                form.labels = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            }
+            ("type", FormKind::Story) => {
+                form.issue_type = parse_choice("type", value, &IssueType::ALL)?
+            }
+            ("severity", FormKind::Story) => {
+                form.severity = parse_choice("severity", value, &Severity::ALL)?
+            }
+            (field, _) => {
+                let def = defs
+                    .iter()
+                    .find(|def| {
+                        def.name
+                            .eq_ignore_ascii_case(field)
+                    })
+                    .ok_or_else(|| format!("unknown field `{}`", field))?;
+                if let Some(value) = def
+                    .parse(value, today())
+                    .map_err(|error| error.to_string())?
+                {
+                    form.fields
+                        .insert(
+                            def.name
+                                .clone(),
+                            value,
+                        );
+                }
+            }
+        }
+    }
+
+    let mut description = vec![];
+    let mut steps = vec![];
+    for line in lines.by_ref() {
+        if kind == FormKind::Story && line.trim() == STEPS_SEPARATOR {
+            steps.extend(lines.by_ref());
+            break;
+        }
+        description.push(line);
+    }
+    form.description = description
+        .join("\n")
+        .trim()
+        .to_owned();
+    form.steps_to_reproduce = steps
+        .join("\n")
+        .trim()
+        .to_owned();
+
+    if form
+        .name
+        .is_empty()
+        && form
+            .description
+            .is_empty()
+    {
+        return Ok(None);
+    }
+    if form
+        .name
+        .is_empty()
+    {
+        return Err("name cannot be empty".to_owned());
+    }
+
+    Ok(Some(form))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::FieldKind;
+
+    fn blank() -> ItemForm {
+        ItemForm::new(String::new(), String::new())
+    }
+
+    #[test]
+    fn parse_form_should_read_fields_and_multiline_description() {
+        let text = "# comment\nname: Login page\npriority: High\n---\n# Acceptance\n\n- works\n- is fast\n";
+
+        assert_eq!(
+            parse_form(text, FormKind::Epic, &[]),
+            Ok(Some(ItemForm {
+                priority: Priority::High,
+                ..ItemForm::new(
+                    "Login page".to_owned(),
+                    "# Acceptance\n\n- works\n- is fast".to_owned()
+                )
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_form_should_read_bug_fields() {
+        let text = render_form("Create story", FormKind::Story, &[], &blank())
+            .replace("name: ", "name: Crash")
+            .replace("type: story", "type: bug")
+            .replace("severity: major", "severity: critical")
+            .replace(
+                STEPS_SEPARATOR,
+                &format!("it crashes\n{}\n1. open\n2. boom", STEPS_SEPARATOR),
+            );
+
+        let story = parse_form(&text, FormKind::Story, &[])
+            .unwrap()
+            .unwrap()
+            .into_story();
+        assert_eq!(story.issue_type, IssueType::Bug);
+        assert_eq!(story.description, "it crashes");
+        assert_eq!(
+            story.bug,
+            Some(BugDetails {
+                steps_to_reproduce: "1. open\n2. boom".to_owned(),
+                severity: Severity::Critical,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_form_should_cancel_untouched_template() {
+        assert_eq!(
+            parse_form(
+                &render_form("Create epic", FormKind::Epic, &[], &blank()),
+                FormKind::Epic,
+                &[]
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            parse_form(
+                &render_form("Create story", FormKind::Story, &[], &blank()),
+                FormKind::Story,
+                &[]
+            ),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn parse_form_should_reject_invalid_fields() {
+        assert_eq!(
+            parse_form("name: \n---\ndescription", FormKind::Epic, &[]),
+            Err("name cannot be empty".to_owned())
+        );
+        assert_eq!(
+            parse_form("name: a\npriority: urgent\n---\n", FormKind::Epic, &[]),
+            Err("unknown priority `urgent`, use low, medium or high".to_owned())
+        );
+        assert_eq!(
+            parse_form("name: a\ntype: epic\n---\n", FormKind::Story, &[]),
+            Err("unknown type `epic`, use story, bug, task or spike".to_owned())
+        );
+        assert_eq!(
+            parse_form("name: a\ntype: bug\n", FormKind::Epic, &[]),
+            Err("unknown field `type`".to_owned())
+        );
+        assert_eq!(
+            parse_form("name: a\nowner: me\n", FormKind::Story, &[]),
+            Err("unknown field `owner`".to_owned())
+        );
+    }
+
+    #[test]
+    fn render_filled_form_should_round_trip_template_values() {
+        let template = Template {
+            name: "bug report".to_owned(),
+            description: "## Expected\n\n## Actual".to_owned(),
+            priority: Priority::High,
+            issue_type: IssueType::Bug,
+            labels: vec!["triage".to_owned(), "ui".to_owned()],
+            ..Template::default()
+        };
+        let mut form = ItemForm::from_template(&template, &[]);
+        form.name = "Crash on save".to_owned();
+
+        let text = render_form("Create story", FormKind::Story, &[], &form);
+
+        assert!(text.contains("labels: triage, ui\n"));
+        assert_eq!(parse_form(&text, FormKind::Story, &[]), Ok(Some(form)));
+    }
+
+    #[test]
+    fn parse_form_should_read_custom_fields() {
+        let defs = [
+            FieldDef::new("customer", FieldKind::Text),
+            FieldDef::new("billable", FieldKind::Bool),
+        ];
+        let text = render_form("Create epic", FormKind::Epic, &defs, &blank())
+            .replace("name: ", "name: Portal")
+            .replace("customer: ", "customer: Acme");
+
+        let epic = parse_form(&text, FormKind::Epic, &defs)
+            .unwrap()
+            .unwrap()
+            .into_epic();
+        assert_eq!(
+            epic.fields,
+            BTreeMap::from([("customer".to_owned(), "Acme".to_owned())])
+        );
+        assert_eq!(
+            parse_form("name: a\nbillable: maybe\n", FormKind::Epic, &defs),
+            Err("billable: expected yes or no but found `maybe`".to_owned())
+        );
+    }
+
+    #[test]
+    fn with_error_should_replace_previous_error() {
+        let text = with_error("name: \n---\ntext", "first");
+        let text = with_error(&text, "second");
+
+        assert_eq!(text, "# error: second\nname: \n---\ntext\n");
+        assert_eq!(
+            parse_form(&text, FormKind::Epic, &[]),
+            Err("name cannot be empty".to_owned())
+        );
+    }
+}
@@ -1,5 +1,6 @@
+mod editor;
 mod pages;
 mod prompts;
 
 pub use pages::*;
-pub use prompts::*;
\ No newline at end of file
+pub use prompts::*;
@@ -0,0 +1,270 @@
+use std::any::Any;
+use std::rc::Rc;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+
+use super::page_helpers::{Column, Frame, Selection, Table};
+use super::{due_cell, navigate_to_story, status_row, Page};
+use crate::dates::{end_of_week, is_overdue, today};
+use crate::db::JiraHandle;
+use crate::models::{Action, DBState, Priority, Status};
+
+// due | kind | id | name | status listing of the agenda
+fn agenda_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("due", 10, 10),
+            Column::new("kind", 6, 5),
+            Column::new("id", 8, 4),
+            Column::new("name", 32, 8),
+            Column::new("status", 17, 11),
+        ],
+        width,
+    )
+}
+
+// an epic or story on the agenda
+struct Entry<'a> {
+    id: u32,
+    kind: &'static str,
+    name: &'a str,
+    due: NaiveDate,
+    status: Status,
+    priority: Priority,
+}
+
+// unfinished epics and stories due by `until`, overdue ones included, by
+// due date; epics and stories share one id sequence so ids stay unique
+fn entries(db_state: &DBState, until: NaiveDate) -> Vec<Entry<'_>> {
+    let epics = db_state
+        .epics
+        .iter()
+        .filter_map(|(id, epic)| {
+            Some(Entry {
+                id: *id,
+                kind: "epic",
+                name: &epic.name,
+                due: epic.due?,
+                status: epic.status,
+                priority: epic.priority,
+            })
+        });
+    let stories = db_state
+        .stories
+        .iter()
+        .filter_map(|(id, story)| {
+            Some(Entry {
+                id: *id,
+                kind: "story",
+                name: &story.name,
+                due: story.due?,
+                status: story.status,
+                priority: story.priority,
+            })
+        });
+    let mut entries: Vec<Entry> = epics
+        .chain(stories)
+        .filter(|entry| {
+            entry.due <= until
+                && !entry
+                    .status
+                    .is_done()
+        })
+        .collect();
+    entries.sort_by_key(|entry| (entry.due, entry.id));
+    entries
+}
+
+// what is due this week, overdue items first
+pub struct AgendaPage {
+    pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+}
+
+impl AgendaPage {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        AgendaPage {
+            db,
+            selection: Selection::default(),
+        }
+    }
+}
+
+impl Page for AgendaPage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let today = today();
+        let until = end_of_week(today);
+        let entries = entries(&db_state, until);
+        let selected = self
+            .selection
+            .selected(&self.selectable_ids()?);
+
+        let table = agenda_table(frame.width());
+        frame.title("AGENDA");
+        frame.line(format!("due by {}", until));
+        frame.line(table.header());
+        for entry in entries.iter() {
+            status_row(
+                frame,
+                &table,
+                &[
+                    &due_cell(Some(entry.due)),
+                    entry.kind,
+                    &entry
+                        .id
+                        .to_string(),
+                    entry.name,
+                    &entry
+                        .status
+                        .to_string(),
+                ],
+                entry.status,
+                entry.priority,
+                is_overdue(Some(entry.due), entry.status, today).then_some(0),
+                selected == Some(entry.id),
+            );
+        }
+        if entries.is_empty() {
+            frame.line("nothing due this week");
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[p] previous | [enter] or [:id:] navigate to item");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            input => {
+                let Ok(id) = input.parse::<u32>() else {
+                    return Ok(None);
+                };
+                if !self
+                    .selectable_ids()?
+                    .contains(&id)
+                {
+                    return Ok(None);
+                }
+                let db_state = self
+                    .db
+                    .read_full_record()?;
+                if db_state
+                    .epics
+                    .contains_key(&id)
+                {
+                    Ok(Some(Action::NavigateToEpicDetail { epic_id: id }))
+                } else {
+                    Ok(Some(navigate_to_story(&db_state, id)))
+                }
+            }
+        }
+    }
+
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        Ok(entries(&db_state, end_of_week(today()))
+            .iter()
+            .map(|entry| entry.id)
+            .collect())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use crate::models::{Epic, Story};
+    use crate::ui::Tone;
+    use chrono::Days;
+
+    #[test]
+    fn agenda_should_list_unfinished_items_due_this_week_by_date() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let today = today();
+        let epic_id = db
+            .create_epic(Epic::new("release".to_owned(), "".to_owned()))
+            .unwrap();
+        let overdue = db
+            .create_story(Story::new("late".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let done = db
+            .create_backlog_story(Story::new("done".to_owned(), "".to_owned()))
+            .unwrap();
+        let later = db
+            .create_backlog_story(Story::new("later".to_owned(), "".to_owned()))
+            .unwrap();
+        db.update_epic_due(epic_id, Some(end_of_week(today)))
+            .unwrap();
+        db.update_story_due(overdue, today.checked_sub_days(Days::new(1)))
+            .unwrap();
+        db.update_story_due(done, Some(today))
+            .unwrap();
+        db.update_story_status(done, Status::Closed)
+            .unwrap();
+        db.update_story_due(later, today.checked_add_days(Days::new(8)))
+            .unwrap();
+
+        let page = AgendaPage::new(Rc::clone(&db));
+        assert_eq!(
+            page.selectable_ids()
+                .unwrap(),
+            [overdue, epic_id]
+        );
+
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+        let late_row = frame
+            .lines()
+            .iter()
+            .position(|line| line.contains("late"))
+            .unwrap();
+        assert!(frame
+            .spans(late_row)
+            .iter()
+            .any(|span| span.tone == Tone::Alert
+                && span
+                    .range
+                    .start
+                    == 0));
+
+        assert_eq!(
+            page.handle_input(&overdue.to_string())
+                .unwrap(),
+            Some(Action::NavigateToStoryDetail {
+                epic_id,
+                story_id: overdue
+            })
+        );
+        assert_eq!(
+            page.handle_input(&epic_id.to_string())
+                .unwrap(),
+            Some(Action::NavigateToEpicDetail { epic_id })
+        );
+        assert_eq!(
+            page.handle_input(&later.to_string())
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use std::any::Any;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use super::page_helpers::{display_width, get_column_string, Frame};
+use super::Page;
+use crate::charts::{
+    epic_burndown, sprint_burndown, velocity, Burndown, VelocityBar, VELOCITY_SPRINTS,
+};
+use crate::dates::today;
+use crate::db::JiraHandle;
+use crate::models::Action;
+
+// rows the burndown plot takes up
+const CHART_HEIGHT: usize = 10;
+// "1234 |" in front of every plot row
+const AXIS_WIDTH: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartScope {
+    Epic(u32),
+    Sprint(u32),
+}
+
+// burndown of an epic or sprint above the velocity of the last sprints
+pub struct ChartPage {
+    pub scope: ChartScope,
+    pub db: Rc<JiraHandle>,
+}
+
+impl ChartPage {
+    pub fn new(scope: ChartScope, db: Rc<JiraHandle>) -> Self {
+        ChartPage { scope, db }
+    }
+}
+
+impl Page for ChartPage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let today = today();
+        let burndown = match self.scope {
+            ChartScope::Epic(epic_id) => epic_burndown(&db_state, epic_id, today)?,
+            ChartScope::Sprint(sprint_id) => sprint_burndown(&db_state, sprint_id, today)?,
+        };
+
+        frame.title("BURNDOWN");
+        frame.line(&burndown.title);
+        frame.line("");
+        for line in burndown_lines(&burndown, frame.width()) {
+            frame.line(line);
+        }
+        let remaining = burndown
+            .remaining
+            .iter()
+            .rev()
+            .flatten()
+            .next()
+            .copied()
+            .unwrap_or_default();
+        frame.line(format!(
+            "remaining: {} of {} points",
+            remaining, burndown.total
+        ));
+
+        frame.line("");
+        frame.title("VELOCITY");
+        let bars = velocity(&db_state, VELOCITY_SPRINTS);
+        if bars.is_empty() {
+            frame.line("no completed sprints yet");
+        }
+        for line in velocity_lines(&bars, frame.width()) {
+            frame.line(line);
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[p] previous");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            _ => Ok(None),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// one column per day, squeezed to fit the width; bars show the remaining
+// points and dots the ideal line
+fn burndown_lines(burndown: &Burndown, width: usize) -> Vec<String> {
+    let days = burndown
+        .days
+        .len();
+    let columns = days.min(
+        width
+            .saturating_sub(AXIS_WIDTH)
+            .max(1),
+    );
+    let day_of = |column: usize| match columns {
+        1 => 0,
+        _ => column * (days - 1) / (columns - 1),
+    };
+    let max = burndown
+        .remaining
+        .iter()
+        .flatten()
+        .copied()
+        .max()
+        .unwrap_or_default()
+        .max(burndown.total)
+        .max(1) as f64;
+    // rows from the bottom, 1..=CHART_HEIGHT
+    let height = |points: f64| (points / max * CHART_HEIGHT as f64).round() as usize;
+
+    let mut lines: Vec<String> = (1..=CHART_HEIGHT)
+        .rev()
+        .map(|row| {
+            let label = match row {
+                CHART_HEIGHT => format!("{:>4} |", max),
+                1 => format!("{:>4} |", 0),
+                _ => "     |".to_owned(),
+            };
+            let cells: String = (0..columns)
+                .map(|column| {
+                    let day = day_of(column);
+                    let filled = burndown.remaining[day]
+                        .is_some_and(|remaining| height(remaining as f64) >= row);
+                    let ideal = burndown
+                        .ideal_at(day)
+                        .is_some_and(|ideal| height(ideal).max(1) == row);
+                    match (filled, ideal) {
+                        (true, _) => '█',
+                        (false, true) => '·',
+                        (false, false) => ' ',
+                    }
+                })
+                .collect();
+            format!("{}{}", label, cells.trim_end())
+        })
+        .collect();
+
+    lines.push(format!("     +{}", "-".repeat(columns)));
+    if let (Some(first), Some(last)) = (
+        burndown
+            .days
+            .first(),
+        burndown
+            .days
+            .last(),
+    ) {
+        let first = first.to_string();
+        let last = last.to_string();
+        let gap = (columns + 1).saturating_sub(display_width(&first) + display_width(&last));
+        lines.push(match days {
+            1 => format!("      {}", first),
+            _ => format!("      {}{}{}", first, " ".repeat(gap.max(1)), last),
+        });
+    }
+    lines
+}
+
+// "name | █████░░░ 5/8": completed points solid, the rest of the commitment
+// shaded
+fn velocity_lines(bars: &[VelocityBar], width: usize) -> Vec<String> {
+    let name_width = 12;
+    let bar_width = width
+        .saturating_sub(name_width + 12)
+        .max(1);
+    let max = bars
+        .iter()
+        .map(|bar| bar.committed)
+        .max()
+        .unwrap_or_default()
+        .max(1) as usize;
+    bars.iter()
+        .map(|bar| {
+            let committed = bar.committed as usize * bar_width / max;
+            let completed = (bar.completed as usize * bar_width / max).min(committed);
+            format!(
+                "{} | {}{} {}/{}",
+                get_column_string(&bar.name, name_width),
+                "█".repeat(completed),
+                "░".repeat(committed - completed),
+                bar.completed,
+                bar.committed
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use crate::models::{Epic, Story};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn burndown_lines_should_draw_bars_and_ideal_line() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let burndown = Burndown {
+            title: "".to_owned(),
+            days: start
+                .iter_days()
+                .take(3)
+                .collect(),
+            remaining: vec![Some(10), Some(10), None],
+            total: 10,
+            ideal: true,
+        };
+
+        let lines = burndown_lines(&burndown, 40);
+
+        assert_eq!(lines[0], "  10 |██");
+        assert_eq!(lines[4], "     |██");
+        assert_eq!(lines[9], "   0 |██·");
+        assert_eq!(lines[10], "     +---");
+        assert_eq!(lines[11], "      2024-01-01 2024-01-03");
+    }
+
+    #[test]
+    fn velocity_lines_should_scale_to_the_largest_commitment() {
+        let bars = [
+            VelocityBar {
+                sprint_id: 1,
+                name: "sprint 1".to_owned(),
+                committed: 8,
+                completed: 4,
+            },
+            VelocityBar {
+                sprint_id: 2,
+                name: "sprint 2".to_owned(),
+                committed: 4,
+                completed: 4,
+            },
+        ];
+
+        assert_eq!(
+            velocity_lines(&bars, 32),
+            [
+                "sprint 1     | ████░░░░ 4/8".to_owned(),
+                "sprint 2     | ████ 4/4".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn draw_page_should_show_epic_burndown() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("release".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        db.update_story_points(story_id, Some(3))
+            .unwrap();
+
+        let page = ChartPage::new(ChartScope::Epic(epic_id), db);
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+
+        let lines = frame.lines();
+        assert!(lines
+            .iter()
+            .any(|line| line == "Epic 1: release"));
+        assert!(lines
+            .iter()
+            .any(|line| line == "remaining: 3 of 3 points"));
+        assert!(lines
+            .iter()
+            .any(|line| line == "no completed sprints yet"));
+        assert_eq!(
+            page.handle_input("p")
+                .unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use super::page_helpers::{display_width, wrap_text, Tone};
+
+pub struct MarkdownLine {
+    pub text: String,
+    pub tone: Option<Tone>,
+}
+
+fn parser(text: &str) -> Parser<'_> {
+    Parser::new_ext(
+        text,
+        Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH,
+    )
+}
+
+// lays descriptions out for the terminal: paragraphs and list items are
+// wrapped to the width, code blocks are kept verbatim and headings and code
+// are toned so the theme can pick them out
+pub fn render_markdown(text: &str, width: usize) -> Vec<MarkdownLine> {
+    let mut renderer = Renderer {
+        width,
+        lines: vec![],
+        text: String::new(),
+        lists: vec![],
+        marker: None,
+        quotes: 0,
+        heading: false,
+        code_block: false,
+    };
+    for event in parser(text) {
+        renderer.event(event);
+    }
+    renderer.finish()
+}
+
+// (checked, total) over the task list items, if there are any
+pub fn checklist_progress(text: &str) -> Option<(usize, usize)> {
+    let (checked, total) = parser(text).fold((0, 0), |(checked, total), event| match event {
+        Event::TaskListMarker(true) => (checked + 1, total + 1),
+        Event::TaskListMarker(false) => (checked, total + 1),
+        _ => (checked, total),
+    });
+    (total > 0).then_some((checked, total))
+}
+
+struct Renderer {
+    width: usize,
+    lines: Vec<MarkdownLine>,
+    // inline text of the current block, wrapped when the block ends
+    text: String,
+    // next number of each open list, None for bullet lists
+    lists: Vec<Option<u64>>,
+    // bullet, number or checkbox of the item whose first line is pending
+    marker: Option<String>,
+    quotes: usize,
+    heading: bool,
+    code_block: bool,
+}
+
+impl Renderer {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => {
+                self.flush();
+                self.heading = true;
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                self.flush();
+                self.heading = false;
+                self.blank();
+            }
+            Event::End(TagEnd::Paragraph) => {
+                self.flush();
+                if self
+                    .lists
+                    .is_empty()
+                {
+                    self.blank();
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                self.flush();
+                self.lists
+                    .push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                self.flush();
+                self.lists
+                    .pop();
+                if self
+                    .lists
+                    .is_empty()
+                {
+                    self.blank();
+                }
+            }
+            Event::Start(Tag::Item) => {
+                self.flush();
+                let marker = match self
+                    .lists
+                    .last_mut()
+                {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_owned(),
+                };
+                self.marker = Some(marker);
+            }
+            Event::End(TagEnd::Item) => self.flush(),
+            Event::TaskListMarker(checked) => {
+                self.marker = Some(if checked { "[x] " } else { "[ ] " }.to_owned());
+            }
+            Event::Start(Tag::BlockQuote(_)) => {
+                self.flush();
+                self.quotes += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                self.flush();
+                self.quotes -= 1;
+                self.blank();
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                self.flush();
+                self.code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                self.code_block = false;
+                self.blank();
+            }
+            Event::Text(text) if self.code_block => {
+                let indent = self.indent();
+                for line in text.lines() {
+                    self.push(format!("{}    {}", indent, line), Some(Tone::Code));
+                }
+            }
+            Event::Text(text) => self
+                .text
+                .push_str(&text),
+            Event::Code(code) => {
+                self.text
+                    .push('`');
+                self.text
+                    .push_str(&code);
+                self.text
+                    .push('`');
+            }
+            Event::SoftBreak => self
+                .text
+                .push(' '),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.flush();
+                self.push("-".repeat(self.width), None);
+                self.blank();
+            }
+            _ => {}
+        }
+    }
+
+    fn indent(&self) -> String {
+        format!(
+            "{}{}",
+            "  ".repeat(
+                self.lists
+                    .len()
+                    .saturating_sub(1)
+            ),
+            "> ".repeat(self.quotes)
+        )
+    }
+
+    fn flush(&mut self) {
+        let text = std::mem::take(&mut self.text);
+        let marker = self
+            .marker
+            .take();
+        if text
+            .trim()
+            .is_empty()
+            && marker.is_none()
+        {
+            return;
+        }
+
+        let indent = self.indent();
+        let marker = marker.unwrap_or_default();
+        let first = format!("{}{}", indent, marker);
+        let rest = format!("{}{}", indent, " ".repeat(display_width(&marker)));
+        let tone = self
+            .heading
+            .then_some(Tone::Heading);
+
+        let wrap_width = self
+            .width
+            .saturating_sub(display_width(&first));
+        for (i, line) in wrap_text(&text, wrap_width)
+            .into_iter()
+            .enumerate()
+        {
+            let prefix = if i == 0 { &first } else { &rest };
+            self.push(format!("{}{}", prefix, line), tone);
+        }
+    }
+
+    fn push(&mut self, text: String, tone: Option<Tone>) {
+        self.lines
+            .push(MarkdownLine { text, tone });
+    }
+
+    // separates blocks without stacking up empty lines
+    fn blank(&mut self) {
+        if self
+            .lines
+            .last()
+            .is_some_and(|line| {
+                !line
+                    .text
+                    .is_empty()
+            })
+        {
+            self.push(String::new(), None);
+        }
+    }
+
+    fn finish(mut self) -> Vec<MarkdownLine> {
+        self.flush();
+        while self
+            .lines
+            .last()
+            .is_some_and(|line| {
+                line.text
+                    .is_empty()
+            })
+        {
+            self.lines
+                .pop();
+        }
+        self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[MarkdownLine]) -> Vec<&str> {
+        lines
+            .iter()
+            .map(|line| {
+                line.text
+                    .as_str()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn render_markdown_should_lay_out_blocks() {
+        let text = "# Goal\n\nShip the login page soon.\n\n1. design\n2. build\n   - api\n\n```\nfn main() {}\n```\n\n> quoted";
+        let lines = render_markdown(text, 20);
+
+        assert_eq!(
+            texts(&lines),
+            [
+                "Goal",
+                "",
+                "Ship the login page",
+                "soon.",
+                "",
+                "1. design",
+                "2. build",
+                "  - api",
+                "",
+                "    fn main() {}",
+                "",
+                "> quoted",
+            ]
+        );
+        assert_eq!(lines[0].tone, Some(Tone::Heading));
+        assert_eq!(lines[9].tone, Some(Tone::Code));
+        assert_eq!(lines[2].tone, None);
+    }
+
+    #[test]
+    fn render_markdown_should_wrap_list_items_under_their_marker() {
+        let lines = render_markdown("- [x] write the tests first\n- [ ] ship", 16);
+
+        assert_eq!(
+            texts(&lines),
+            ["[x] write the", "    tests first", "[ ] ship"]
+        );
+    }
+
+    #[test]
+    fn checklist_progress_should_count_task_items() {
+        assert_eq!(
+            checklist_progress("- [x] one\n- [ ] two\n- [X] three\n- plain"),
+            Some((2, 3))
+        );
+        assert_eq!(checklist_progress("no tasks, just [x] text"), None);
+    }
+}
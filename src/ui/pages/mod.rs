@@ -1,71 +1,580 @@
 use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
+use crate::config::DEFAULT_PAGE_SIZE;
+use crate::dates::{is_overdue, today};
 use crate::db::*;
-use crate::models::Action;
+use crate::fields::FieldFilter;
+use crate::models::{
+    Action, DBState, Epic, IssueType, ItemKey, Priority, Status, Story, SubTask, Timer,
+};
+use crate::reports::format_minutes;
 use anyhow::anyhow;
 use anyhow::Result;
-use itertools::Itertools;
+use chrono::{DateTime, Local, NaiveDate, Utc};
 
+mod agenda;
+mod charts;
+mod markdown;
 mod page_helpers;
+mod projects;
+mod reports;
+mod sprints;
+pub use agenda::*;
+pub use charts::*;
+use markdown::*;
 use page_helpers::*;
+pub use page_helpers::{display_width, truncate_to_width, wrap_text, Frame, Selection, Span, Tone};
+pub use projects::*;
+pub use reports::*;
+pub use sprints::*;
 
 pub trait Page {
-    fn draw_page(&self) -> Result<()>;
+    fn draw_page(&self, frame: &mut Frame) -> Result<()>;
     fn handle_input(&self, input: &str) -> Result<Option<Action>>;
+    // ids of the rows the cursor moves over, in the order they are drawn;
+    // pressing enter on a row sends its id to handle_input
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        Ok(vec![])
+    }
+    fn selection(&self) -> Option<&Selection> {
+        None
+    }
     fn as_any(&self) -> &dyn Any;
 }
 
+// id | name | due | status listing used for epics and stories
+fn list_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("id", 11, 4),
+            Column::new("name", 32, 8),
+            Column::new("due", 10, 10),
+            Column::new("status", 17, 11),
+        ],
+        width,
+    )
+}
+
+// id | type | name | due | status listing of stories
+fn story_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("id", 11, 4),
+            Column::new("type", 8, 5),
+            Column::new("name", 32, 8),
+            Column::new("due", 10, 10),
+            Column::new("status", 17, 11),
+        ],
+        width,
+    )
+}
+
+// all types, then each type in turn
+fn next_type_filter(filter: Option<IssueType>) -> Option<IssueType> {
+    let position = filter.and_then(|filter| {
+        IssueType::ALL
+            .iter()
+            .position(|issue_type| *issue_type == filter)
+    });
+    match position {
+        None => Some(IssueType::ALL[0]),
+        Some(index) => IssueType::ALL
+            .get(index + 1)
+            .copied(),
+    }
+}
+
+// single item table on detail pages; the description wraps instead of being
+// cut off
+fn detail_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("id", 5, 4),
+            Column::new("name", 12, 8),
+            Column::new("description", 27, 11).wrapped(),
+            Column::new("status", 13, 11),
+        ],
+        width,
+    )
+}
+
+// adds a table row whose last column is the status; the status cell is
+// toned by its value, high priority rows are flagged as a whole and
+// `alert_column`, e.g. an overdue date, is flagged on its own
+fn status_row(
+    frame: &mut Frame,
+    table: &Table,
+    cells: &[&str],
+    status: Status,
+    priority: Priority,
+    alert_column: Option<usize>,
+    highlighted: bool,
+) {
+    let status_column = cells.len() - 1;
+    for (i, line) in table
+        .row(cells)
+        .into_iter()
+        .enumerate()
+    {
+        let status_range = table.cell_range(&line, status_column);
+        let alert_range = alert_column.map(|column| table.cell_range(&line, column));
+        frame.row(line, highlighted && i == 0);
+        if priority == Priority::High {
+            frame.tone_line(Tone::Alert);
+        }
+        if i == 0 {
+            frame.tone_range(status_range, Tone::Status(status));
+            if let Some(range) = alert_range {
+                frame.tone_range(range, Tone::Alert);
+            }
+        }
+    }
+}
+
+// due column cell, "-" when there is no due date
+fn due_cell(due: Option<NaiveDate>) -> String {
+    due.map_or("-".to_owned(), |due| due.to_string())
+}
+
+// custom field values as `name: value` pairs, None when there are none
+fn fields_line(fields: &BTreeMap<String, String>) -> Option<String> {
+    if fields.is_empty() {
+        return None;
+    }
+    let pairs: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+    Some(format!("fields: {}", pairs.join(" | ")))
+}
+
+// names are prefixed with the key of items in a project, e.g. `API-42 name`
+fn keyed_name(key: Option<&ItemKey>, name: &str) -> String {
+    match key {
+        Some(key) => format!("{} {}", key, name),
+        None => name.to_owned(),
+    }
+}
+
+// whether an item belongs to the project listing; every item does when no
+// project is picked
+fn in_project(key: Option<&ItemKey>, project: Option<&str>) -> bool {
+    project.is_none_or(|project| key.is_some_and(|key| key.project == project))
+}
+
+// the due column, second to last, when the item is overdue
+fn overdue_column(cells: usize, due: Option<NaiveDate>, status: Status) -> Option<usize> {
+    is_overdue(due, status, today()).then_some(cells - 2)
+}
+
+// adds a story to a story_table listing; stories with open blockers are
+// marked as blocked
+fn story_row(
+    frame: &mut Frame,
+    table: &Table,
+    id: u32,
+    story: &Story,
+    db_state: &DBState,
+    highlighted: bool,
+) {
+    let mut name = story_name(story, &db_state.subtasks);
+    if !db_state
+        .open_blockers(id)
+        .is_empty()
+    {
+        name.push_str(" (blocked)");
+    }
+    status_row(
+        frame,
+        table,
+        &[
+            &id.to_string(),
+            &story
+                .issue_type
+                .to_string(),
+            &name,
+            &due_cell(story.due),
+            &story
+                .status
+                .to_string(),
+        ],
+        story.status,
+        story.priority,
+        overdue_column(5, story.due, story.status),
+        highlighted,
+    );
+}
+
+// story detail of a story wherever it lives
+fn navigate_to_story(db_state: &DBState, story_id: u32) -> Action {
+    match db_state.epic_of_story(story_id) {
+        Some(epic_id) => Action::NavigateToStoryDetail { epic_id, story_id },
+        None => Action::NavigateToBacklogStory { story_id },
+    }
+}
+
+// story names carry the progress of the checklist in their description and
+// of their sub-tasks
+fn story_name(story: &Story, subtasks: &HashMap<u32, SubTask>) -> String {
+    let mut name = keyed_name(
+        story
+            .key
+            .as_ref(),
+        &story.name,
+    );
+    if let Some((checked, total)) = checklist_progress(&story.description) {
+        name.push_str(&format!(" [{}/{}]", checked, total));
+    }
+    if !story
+        .subtasks
+        .is_empty()
+    {
+        let done = story
+            .subtasks
+            .iter()
+            .filter(|id| {
+                subtasks
+                    .get(id)
+                    .is_some_and(|subtask| {
+                        subtask
+                            .status
+                            .is_done()
+                    })
+            })
+            .count();
+        name.push_str(&format!(
+            " ({}/{} done)",
+            done,
+            story
+                .subtasks
+                .len()
+        ));
+    }
+    name
+}
+
+fn markdown_lines(frame: &mut Frame, text: &str) {
+    for line in render_markdown(text, frame.width()) {
+        frame.line(line.text);
+        if let Some(tone) = line.tone {
+            frame.tone_line(tone);
+        }
+    }
+}
+
+// user | start | time | note listing of a story's worklogs
+fn worklog_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("user", 10, 6),
+            Column::new("start", 16, 16),
+            Column::new("time", 8, 7),
+            Column::new("note", 30, 6),
+        ],
+        width,
+    )
+}
+
+// timestamps are stored in utc and shown in the local time zone
+fn local_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+// link | id | name | status listing of a story's links
+fn link_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("link", 16, 13),
+            Column::new("id", 5, 4),
+            Column::new("name", 28, 8),
+            Column::new("status", 17, 11),
+        ],
+        width,
+    )
+}
+
+// links of a story as seen from it, e.g. "is blocked by" for the target of
+// a blocks link, by linked story id
+fn story_links(db_state: &DBState, story_id: u32) -> Vec<(String, u32)> {
+    let mut links: Vec<(String, u32)> = db_state
+        .links
+        .iter()
+        .filter_map(|link| {
+            if link.from == story_id {
+                Some((
+                    link.kind
+                        .to_string(),
+                    link.to,
+                ))
+            } else if link.to == story_id {
+                Some((
+                    link.kind
+                        .inverse()
+                        .to_owned(),
+                    link.from,
+                ))
+            } else {
+                None
+            }
+        })
+        .collect();
+    links.sort_by_key(|(_, id)| *id);
+    links
+}
+
+// id | name | assignee | status listing of a story's sub-tasks
+fn subtask_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("id", 5, 4),
+            Column::new("name", 30, 8),
+            Column::new("assignee", 14, 8),
+            Column::new("status", 17, 11),
+        ],
+        width,
+    )
+}
+
+// fields list pages can be sorted by
+trait Listed {
+    fn name(&self) -> &str;
+    fn status(&self) -> Status;
+    fn priority(&self) -> Priority;
+    fn created_at(&self) -> Option<DateTime<Utc>>;
+}
+
+impl Listed for Epic {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn status(&self) -> Status {
+        self.status
+    }
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+}
+
+impl Listed for Story {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn status(&self) -> Status {
+        self.status
+    }
+    fn priority(&self) -> Priority {
+        self.priority
+    }
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+}
+
+// ids in display order; ties, and items without a creation date, fall back
+// to id order
+fn sort_ids<T: Listed>(
+    items: &HashMap<u32, T>,
+    ids: impl IntoIterator<Item = u32>,
+    key: SortKey,
+) -> Vec<u32> {
+    let mut ids: Vec<u32> = ids
+        .into_iter()
+        .filter(|id| items.contains_key(id))
+        .collect();
+    ids.sort_by(|a, b| {
+        let (x, y) = (&items[a], &items[b]);
+        let order = match key {
+            SortKey::Id => Ordering::Equal,
+            SortKey::Name => x
+                .name()
+                .to_lowercase()
+                .cmp(
+                    &y.name()
+                        .to_lowercase(),
+                ),
+            SortKey::Status => x
+                .status()
+                .cmp(&y.status()),
+            SortKey::Created => x
+                .created_at()
+                .cmp(&y.created_at()),
+            // most urgent first
+            SortKey::Priority => y
+                .priority()
+                .cmp(&x.priority()),
+        };
+        order.then(a.cmp(b))
+    });
+    ids
+}
+
 pub struct HomePage {
     pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+    pub listing: Listing,
+    // key of the project whose epics are listed, None for all epics
+    pub project: Option<String>,
 }
 
-impl Page for HomePage {
-    fn draw_page(&self) -> Result<()> {
-        println!("----------------------------- EPICS -----------------------------");
-        println!("     id     |               name               |      status      ");
+impl HomePage {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        HomePage::with_page_size(db, DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn with_page_size(db: Rc<JiraHandle>, page_size: usize) -> Self {
+        HomePage::in_project(db, None, page_size)
+    }
 
+    pub fn in_project(db: Rc<JiraHandle>, project: Option<String>, page_size: usize) -> Self {
+        HomePage {
+            db,
+            selection: Selection::default(),
+            listing: Listing::new(page_size),
+            project,
+        }
+    }
+
+    fn sorted_ids(&self) -> Result<Vec<u32>> {
         let epics = self
             .db
             .read_full_record()?
             .epics;
+        let ids: Vec<u32> = epics
+            .iter()
+            .filter(|(_, epic)| {
+                in_project(
+                    epic.key
+                        .as_ref(),
+                    self.project
+                        .as_deref(),
+                )
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        Ok(sort_ids(
+            &epics,
+            ids,
+            self.listing
+                .sort_key(),
+        ))
+    }
+}
 
-        for id in epics
-            .keys()
-            .sorted()
-        {
+impl Page for HomePage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let table = list_table(frame.width());
+        let db_state = self
+            .db
+            .read_full_record()?;
+        match self
+            .project
+            .as_ref()
+            .and_then(|key| {
+                db_state
+                    .projects
+                    .get(key)
+                    .map(|project| (key, project))
+            }) {
+            Some((key, project)) => frame.title(&format!("EPICS OF {}: {}", key, project.name)),
+            None => frame.title("EPICS"),
+        }
+        frame.line(table.header());
+
+        let epics = db_state.epics;
+        let all_ids = self.sorted_ids()?;
+        let ids = self
+            .listing
+            .visible(&all_ids);
+        let selected = self
+            .selection
+            .selected(ids);
+
+        for id in ids.iter() {
             let epic = &epics[id];
-            let id_col = get_column_string(&id.to_string(), 11);
-            let name_col = get_column_string(&epic.name, 32);
-            let status_col = get_column_string(
-                &epic
-                    .status
-                    .to_string(),
-                17,
+            status_row(
+                frame,
+                &table,
+                &[
+                    &id.to_string(),
+                    &keyed_name(
+                        epic.key
+                            .as_ref(),
+                        &epic.name,
+                    ),
+                    &due_cell(epic.due),
+                    &epic
+                        .status
+                        .to_string(),
+                ],
+                epic.status,
+                epic.priority,
+                overdue_column(4, epic.due, epic.status),
+                selected == Some(*id),
             );
-            println!("{} | {} | {}", id_col, name_col, status_col);
         }
 
-        println!();
-        println!();
+        frame.line("");
+        frame.line(
+            self.listing
+                .summary(all_ids.len()),
+        );
+        frame.line("");
 
-        println!("[q] quit | [c] create epic | [:id:] navigate to epic");
+        frame.line("[q] quit | [c] create epic | [b] backlog | [j] projects | [t] sprints | [a] agenda | [r] time report | [n] next page | [p] previous page | [s] sort | [enter] or [:id:] navigate to epic");
 
         Ok(())
     }
 
     fn handle_input(&self, input: &str) -> Result<Option<Action>> {
-        let epics = self
-            .db
-            .read_full_record()?
-            .epics;
+        let all_ids = self.sorted_ids()?;
 
         match input {
             "q" => Ok(Some(Action::Exit)),
             "c" => Ok(Some(Action::CreateEpic)),
+            "b" => Ok(Some(Action::NavigateToBacklog)),
+            "j" => Ok(Some(Action::NavigateToProjects)),
+            "t" => Ok(Some(Action::NavigateToSprints)),
+            "a" => Ok(Some(Action::NavigateToAgenda)),
+            "r" => Ok(Some(Action::NavigateToTimeReport)),
+            "n" => {
+                if self
+                    .listing
+                    .next_page(all_ids.len())
+                {
+                    self.selection
+                        .reset();
+                }
+                Ok(None)
+            }
+            "p" => {
+                if self
+                    .listing
+                    .previous_page(all_ids.len())
+                {
+                    self.selection
+                        .reset();
+                }
+                Ok(None)
+            }
+            "s" => {
+                self.listing
+                    .cycle_sort();
+                self.selection
+                    .reset();
+                Ok(None)
+            }
             input => {
                 if let Ok(epic_id) = input.parse::<u32>() {
-                    if epics.contains_key(&epic_id) {
+                    if all_ids.contains(&epic_id) {
                         return Ok(Some(Action::NavigateToEpicDetail { epic_id }));
                     }
                 }
@@ -74,6 +583,17 @@ impl Page for HomePage {
         }
     }
 
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        Ok(self
+            .listing
+            .visible(&self.sorted_ids()?)
+            .to_vec())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -82,10 +602,40 @@ impl Page for HomePage {
 pub struct EpicDetail {
     pub epic_id: u32,
     pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+    pub listing: Listing,
+    // only stories of this type are listed when set
+    pub type_filter: Cell<Option<IssueType>>,
+    // only stories matching this custom field value are listed when set
+    pub field_filter: RefCell<Option<FieldFilter>>,
 }
 
-impl Page for EpicDetail {
-    fn draw_page(&self) -> Result<()> {
+impl EpicDetail {
+    pub fn new(epic_id: u32, db: Rc<JiraHandle>) -> Self {
+        EpicDetail::with_page_size(epic_id, db, DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn with_page_size(epic_id: u32, db: Rc<JiraHandle>, page_size: usize) -> Self {
+        EpicDetail {
+            epic_id,
+            db,
+            selection: Selection::default(),
+            listing: Listing::new(page_size),
+            type_filter: Cell::new(None),
+            field_filter: RefCell::new(None),
+        }
+    }
+
+    pub fn set_field_filter(&self, filter: Option<FieldFilter>) {
+        self.field_filter
+            .replace(filter);
+        self.listing
+            .first_page();
+        self.selection
+            .reset();
+    }
+
+    fn sorted_ids(&self) -> Result<Vec<u32>> {
         let db_state = self
             .db
             .read_full_record()?;
@@ -93,54 +643,148 @@ impl Page for EpicDetail {
             .epics
             .get(&self.epic_id)
             .ok_or_else(|| anyhow!("could not find epic!"))?;
+        let type_filter = self
+            .type_filter
+            .get();
+        let field_filter = self
+            .field_filter
+            .borrow();
+        Ok(sort_ids(
+            &db_state.stories,
+            epic.stories
+                .iter()
+                .copied()
+                .filter(|id| {
+                    type_filter.is_none()
+                        || db_state
+                            .stories
+                            .get(id)
+                            .map(|story| story.issue_type)
+                            == type_filter
+                })
+                .filter(|id| {
+                    field_filter
+                        .as_ref()
+                        .is_none_or(|filter| {
+                            db_state
+                                .stories
+                                .get(id)
+                                .is_some_and(|story| filter.matches(&story.fields))
+                        })
+                }),
+            self.listing
+                .sort_key(),
+        ))
+    }
+}
 
-        println!("------------------------------ EPIC ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
+impl Page for EpicDetail {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let epic = db_state
+            .epics
+            .get(&self.epic_id)
+            .ok_or_else(|| anyhow!("could not find epic!"))?;
 
-        let id_col = get_column_string(
-            &self
-                .epic_id
-                .to_string(),
-            5,
-        );
-        let name_col = get_column_string(&epic.name, 12);
-        let desc_col = get_column_string(&epic.description, 27);
-        let status_col = get_column_string(
-            &epic
-                .status
-                .to_string(),
-            13,
+        let table = detail_table(frame.width());
+        frame.title("EPIC");
+        frame.line(table.header());
+        status_row(
+            frame,
+            &table,
+            &[
+                &self
+                    .epic_id
+                    .to_string(),
+                &keyed_name(
+                    epic.key
+                        .as_ref(),
+                    &epic.name,
+                ),
+                &epic.description,
+                &epic
+                    .status
+                    .to_string(),
+            ],
+            epic.status,
+            epic.priority,
+            None,
+            false,
         );
-        println!("{} | {} | {} | {}", id_col, name_col, desc_col, status_col);
+        let mut details = format!("priority: {}", epic.priority);
+        if !epic
+            .labels
+            .is_empty()
+        {
+            details.push_str(&format!(
+                " | labels: {}",
+                epic.labels
+                    .join(", ")
+            ));
+        }
+        if let Some(due) = epic.due {
+            details.push_str(&format!(" | due: {}", due));
+            if is_overdue(epic.due, epic.status, today()) {
+                details.push_str(" (overdue)");
+            }
+        }
+        frame.line(details);
+        if let Some(fields) = fields_line(&epic.fields) {
+            frame.line(fields);
+        }
 
-        println!();
+        frame.line("");
 
-        println!("---------------------------- STORIES ----------------------------");
-        println!("     id     |               name               |      status      ");
+        let table = story_table(frame.width());
+        frame.title("STORIES");
+        frame.line(table.header());
 
         let stories = &db_state.stories;
-
-        for id in epic
-            .stories
-            .iter()
-            .sorted()
-        {
-            let story = &stories[id];
-            let id_col = get_column_string(&id.to_string(), 11);
-            let name_col = get_column_string(&story.name, 32);
-            let status_col = get_column_string(
-                &story
-                    .status
-                    .to_string(),
-                17,
+        let all_ids = self.sorted_ids()?;
+        let ids = self
+            .listing
+            .visible(&all_ids);
+        let selected = self
+            .selection
+            .selected(ids);
+
+        for id in ids.iter() {
+            story_row(
+                frame,
+                &table,
+                *id,
+                &stories[id],
+                &db_state,
+                selected == Some(*id),
             );
-            println!("{} | {} | {}", id_col, name_col, status_col);
         }
 
-        println!();
-        println!();
+        frame.line("");
+        let mut summary = format!(
+            "{} | type: {}",
+            self.listing
+                .summary(all_ids.len()),
+            self.type_filter
+                .get()
+                .map_or("all".to_owned(), |issue_type| {
+                    issue_type
+                        .to_string()
+                        .to_lowercase()
+                })
+        );
+        if let Some(filter) = self
+            .field_filter
+            .borrow()
+            .as_ref()
+        {
+            summary.push_str(&format!(" | where: {}={}", filter.name, filter.value));
+        }
+        frame.line(summary);
+        frame.line("");
 
-        println!("[p] previous | [u] update epic | [d] delete epic | [c] create story | [:id:] navigate to story");
+        frame.line("[p] previous | [u] update epic | [r] set priority | [w] set due date | [d] delete epic | [c] create story | [n] next page | [s] sort | [f] filter by type | [i] filter by field | [v] set field | [g] charts | [enter] or [:id:] navigate to story");
 
         Ok(())
     }
@@ -150,12 +794,70 @@ impl Page for EpicDetail {
             .db
             .read_full_record()?;
         let stories = db_state.stories;
+        let story_count = self
+            .sorted_ids()?
+            .len();
 
         match input {
-            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            // steps back through the result pages before leaving the epic
+            "p" => {
+                if self
+                    .listing
+                    .previous_page(story_count)
+                {
+                    self.selection
+                        .reset();
+                    return Ok(None);
+                }
+                Ok(Some(Action::NavigateToPreviousPage))
+            }
+            "n" => {
+                if self
+                    .listing
+                    .next_page(story_count)
+                {
+                    self.selection
+                        .reset();
+                }
+                Ok(None)
+            }
+            "s" => {
+                self.listing
+                    .cycle_sort();
+                self.selection
+                    .reset();
+                Ok(None)
+            }
+            "f" => {
+                self.type_filter
+                    .set(next_type_filter(
+                        self.type_filter
+                            .get(),
+                    ));
+                self.listing
+                    .first_page();
+                self.selection
+                    .reset();
+                Ok(None)
+            }
             "u" => Ok(Some(Action::UpdateEpicStatus {
                 epic_id: self.epic_id,
             })),
+            "r" => Ok(Some(Action::UpdateEpicPriority {
+                epic_id: self.epic_id,
+            })),
+            "w" => Ok(Some(Action::UpdateEpicDue {
+                epic_id: self.epic_id,
+            })),
+            "v" => Ok(Some(Action::UpdateEpicField {
+                epic_id: self.epic_id,
+            })),
+            "i" => Ok(Some(Action::FilterEpicStories {
+                epic_id: self.epic_id,
+            })),
+            "g" => Ok(Some(Action::NavigateToEpicCharts {
+                epic_id: self.epic_id,
+            })),
             "d" => Ok(Some(Action::DeleteEpic {
                 epic_id: self.epic_id,
             })),
@@ -176,19 +878,223 @@ impl Page for EpicDetail {
         }
     }
 
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        Ok(self
+            .listing
+            .visible(&self.sorted_ids()?)
+            .to_vec())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// stories that are not attached to an epic yet
+pub struct BacklogPage {
+    pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+    pub listing: Listing,
+    // key of the project whose backlog is listed, None for the whole backlog
+    pub project: Option<String>,
+}
+
+impl BacklogPage {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        BacklogPage::with_page_size(db, DEFAULT_PAGE_SIZE)
+    }
+
+    pub fn with_page_size(db: Rc<JiraHandle>, page_size: usize) -> Self {
+        BacklogPage::in_project(db, None, page_size)
+    }
+
+    pub fn in_project(db: Rc<JiraHandle>, project: Option<String>, page_size: usize) -> Self {
+        BacklogPage {
+            db,
+            selection: Selection::default(),
+            listing: Listing::new(page_size),
+            project,
+        }
+    }
+
+    fn sorted_ids(&self) -> Result<Vec<u32>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let ids: Vec<u32> = db_state
+            .backlog()
+            .into_iter()
+            .filter(|id| {
+                in_project(
+                    db_state.stories[id]
+                        .key
+                        .as_ref(),
+                    self.project
+                        .as_deref(),
+                )
+            })
+            .collect();
+        Ok(sort_ids(
+            &db_state.stories,
+            ids,
+            self.listing
+                .sort_key(),
+        ))
+    }
+}
+
+impl Page for BacklogPage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+
+        let table = story_table(frame.width());
+        match &self.project {
+            Some(key) => frame.title(&format!("BACKLOG OF {}", key)),
+            None => frame.title("BACKLOG"),
+        }
+        frame.line(table.header());
+
+        let all_ids = self.sorted_ids()?;
+        let ids = self
+            .listing
+            .visible(&all_ids);
+        let selected = self
+            .selection
+            .selected(ids);
+
+        for id in ids.iter() {
+            story_row(
+                frame,
+                &table,
+                *id,
+                &db_state.stories[id],
+                &db_state,
+                selected == Some(*id),
+            );
+        }
+
+        frame.line("");
+        frame.line(
+            self.listing
+                .summary(all_ids.len()),
+        );
+        frame.line("");
+
+        frame.line("[p] previous | [c] create story | [a] attach selected story to an epic | [n] next page | [s] sort | [enter] or [:id:] navigate to story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let all_ids = self.sorted_ids()?;
+
+        match input {
+            // steps back through the result pages before leaving the backlog
+            "p" => {
+                if self
+                    .listing
+                    .previous_page(all_ids.len())
+                {
+                    self.selection
+                        .reset();
+                    return Ok(None);
+                }
+                Ok(Some(Action::NavigateToPreviousPage))
+            }
+            "n" => {
+                if self
+                    .listing
+                    .next_page(all_ids.len())
+                {
+                    self.selection
+                        .reset();
+                }
+                Ok(None)
+            }
+            "s" => {
+                self.listing
+                    .cycle_sort();
+                self.selection
+                    .reset();
+                Ok(None)
+            }
+            "c" => Ok(Some(Action::CreateBacklogStory)),
+            "a" => Ok(self
+                .selection
+                .selected(
+                    self.listing
+                        .visible(&all_ids),
+                )
+                .map(|story_id| Action::AttachStory { story_id })),
+            input => {
+                if let Ok(story_id) = input.parse::<u32>() {
+                    if all_ids.contains(&story_id) {
+                        return Ok(Some(Action::NavigateToBacklogStory { story_id }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        Ok(self
+            .listing
+            .visible(&self.sorted_ids()?)
+            .to_vec())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
 
 pub struct StoryDetail {
-    pub epic_id: u32,
+    // None for stories in the backlog
+    pub epic_id: Option<u32>,
     pub story_id: u32,
     pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+}
+
+impl StoryDetail {
+    pub fn new(epic_id: u32, story_id: u32, db: Rc<JiraHandle>) -> Self {
+        StoryDetail {
+            epic_id: Some(epic_id),
+            story_id,
+            db,
+            selection: Selection::default(),
+        }
+    }
+
+    pub fn in_backlog(story_id: u32, db: Rc<JiraHandle>) -> Self {
+        StoryDetail {
+            epic_id: None,
+            story_id,
+            db,
+            selection: Selection::default(),
+        }
+    }
+
+    fn selected_subtask(&self) -> Result<Option<u32>> {
+        Ok(self
+            .selection
+            .selected(&self.selectable_ids()?))
+    }
 }
 
 impl Page for StoryDetail {
-    fn draw_page(&self) -> Result<()> {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
         let db_state = self
             .db
             .read_full_record()?;
@@ -197,28 +1103,170 @@ impl Page for StoryDetail {
             .get(&self.story_id)
             .ok_or_else(|| anyhow!("could not find story!"))?;
 
-        println!("------------------------------ STORY ------------------------------");
-        println!("  id  |     name     |         description         |    status    ");
-        let id_col = get_column_string(
-            &self
-                .story_id
-                .to_string(),
-            5,
-        );
-        let name_col = get_column_string(&story.name, 12);
-        let desc_col = get_column_string(&story.description, 27);
-        let status_col = get_column_string(
-            &story
-                .status
-                .to_string(),
-            13,
-        );
-        println!("{} | {} | {} | {}", id_col, name_col, desc_col, status_col);
+        let table = story_table(frame.width());
+        frame.title("STORY");
+        frame.line(table.header());
+        story_row(frame, &table, self.story_id, story, &db_state, false);
+        let mut details = format!("priority: {}", story.priority);
+        if !story
+            .labels
+            .is_empty()
+        {
+            details.push_str(&format!(
+                " | labels: {}",
+                story
+                    .labels
+                    .join(", ")
+            ));
+        }
+        if let Some(points) = story.points {
+            details.push_str(&format!(" | points: {}", points));
+        }
+        if let Some(bug) = &story.bug {
+            details.push_str(&format!(" | severity: {}", bug.severity));
+        }
+        let logged: u64 = story
+            .worklogs
+            .iter()
+            .map(|worklog| u64::from(worklog.minutes))
+            .sum();
+        if logged > 0 {
+            details.push_str(&format!(" | logged: {}", format_minutes(logged)));
+        }
+        frame.line(details);
+        if let Some(fields) = fields_line(&story.fields) {
+            frame.line(fields);
+        }
+        let blockers = db_state.open_blockers(self.story_id);
+        if !blockers.is_empty() {
+            let ids: Vec<String> = blockers
+                .iter()
+                .map(u32::to_string)
+                .collect();
+            frame.line(format!("blocked by: {}", ids.join(", ")));
+            frame.tone_line(Tone::Alert);
+        }
+        let mut timers: Vec<(&String, &Timer)> = db_state
+            .timers
+            .iter()
+            .filter(|(_, timer)| timer.story_id == self.story_id)
+            .collect();
+        timers.sort_unstable_by_key(|(user, _)| *user);
+        for (user, timer) in timers {
+            frame.line(format!(
+                "timer running: {} since {}",
+                user,
+                local_time(timer.started_at)
+            ));
+        }
+
+        frame.line("");
+        let table = subtask_table(frame.width());
+        frame.title("SUB-TASKS");
+        frame.line(table.header());
+        let selected = self
+            .selection
+            .selected(&story.subtasks);
+        for id in story
+            .subtasks
+            .iter()
+        {
+            let Some(subtask) = db_state
+                .subtasks
+                .get(id)
+            else {
+                continue;
+            };
+            status_row(
+                frame,
+                &table,
+                &[
+                    &id.to_string(),
+                    &subtask.name,
+                    subtask
+                        .assignee
+                        .as_deref()
+                        .unwrap_or("-"),
+                    &subtask
+                        .status
+                        .to_string(),
+                ],
+                subtask.status,
+                Priority::default(),
+                None,
+                selected == Some(*id),
+            );
+        }
+
+        let links = story_links(&db_state, self.story_id);
+        if !links.is_empty() {
+            frame.line("");
+            let table = link_table(frame.width());
+            frame.title("LINKS");
+            frame.line(table.header());
+            for (kind, id) in links {
+                let Some(linked) = db_state
+                    .stories
+                    .get(&id)
+                else {
+                    continue;
+                };
+                status_row(
+                    frame,
+                    &table,
+                    &[
+                        &kind,
+                        &id.to_string(),
+                        &linked.name,
+                        &linked
+                            .status
+                            .to_string(),
+                    ],
+                    linked.status,
+                    Priority::default(),
+                    None,
+                    false,
+                );
+            }
+        }
 
-        println!();
-        println!();
+        frame.line("");
+        frame.title("DESCRIPTION");
+        markdown_lines(frame, &story.description);
 
-        println!("[p] previous | [u] update story | [d] delete story");
+        if let Some(bug) = &story.bug {
+            frame.line("");
+            frame.title("STEPS TO REPRODUCE");
+            markdown_lines(frame, &bug.steps_to_reproduce);
+        }
+
+        if !story
+            .worklogs
+            .is_empty()
+        {
+            frame.line("");
+            let table = worklog_table(frame.width());
+            frame.title("WORKLOG");
+            frame.line(table.header());
+            for worklog in story
+                .worklogs
+                .iter()
+            {
+                for line in table.row(&[
+                    &worklog.user,
+                    &local_time(worklog.start),
+                    &format_minutes(u64::from(worklog.minutes)),
+                    &worklog.note,
+                ]) {
+                    frame.line(line);
+                }
+            }
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[p] previous | [u] update story | [r] set priority | [e] estimate points | [w] set due date | [v] set field | [t] start/stop timer | [l] link story | [o] remove link | [d] delete story | [a] add sub-task | [enter] or [:id:] toggle sub-task | [x] delete sub-task");
 
         Ok(())
     }
@@ -229,24 +1277,87 @@ impl Page for StoryDetail {
             "u" => Ok(Some(Action::UpdateStoryStatus {
                 story_id: self.story_id,
             })),
-            "d" => Ok(Some(Action::DeleteStory {
-                epic_id: self.epic_id,
+            "r" => Ok(Some(Action::UpdateStoryPriority {
+                story_id: self.story_id,
+            })),
+            "w" => Ok(Some(Action::UpdateStoryDue {
+                story_id: self.story_id,
+            })),
+            "v" => Ok(Some(Action::UpdateStoryField {
+                story_id: self.story_id,
+            })),
+            "e" => Ok(Some(Action::UpdateStoryPoints {
+                story_id: self.story_id,
+            })),
+            "d" => Ok(Some(match self.epic_id {
+                Some(epic_id) => Action::DeleteStory {
+                    epic_id,
+                    story_id: self.story_id,
+                },
+                None => Action::DeleteBacklogStory {
+                    story_id: self.story_id,
+                },
+            })),
+            "a" => Ok(Some(Action::CreateSubTask {
+                story_id: self.story_id,
+            })),
+            "t" => Ok(Some(Action::ToggleTimer {
+                story_id: self.story_id,
+            })),
+            "l" => Ok(Some(Action::LinkStory {
+                story_id: self.story_id,
+            })),
+            "o" => Ok(Some(Action::UnlinkStory {
                 story_id: self.story_id,
             })),
-            _ => Ok(None),
+            "x" => Ok(self
+                .selected_subtask()?
+                .map(|subtask_id| Action::DeleteSubTask {
+                    story_id: self.story_id,
+                    subtask_id,
+                })),
+            input => {
+                if let Ok(subtask_id) = input.parse::<u32>() {
+                    if self
+                        .selectable_ids()?
+                        .contains(&subtask_id)
+                    {
+                        return Ok(Some(Action::ToggleSubTask { subtask_id }));
+                    }
+                }
+                Ok(None)
+            }
         }
     }
 
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let story = db_state
+            .stories
+            .get(&self.story_id)
+            .ok_or_else(|| anyhow!("could not find story!"))?;
+        Ok(story
+            .subtasks
+            .clone())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
     use crate::db::tests::test_utils::MockDB;
-    use crate::models::{Epic, Story};
+    use crate::models::{BugDetails, Epic, LinkKind, Severity, Story, SubTask};
 
     mod home_page {
         use super::*;
@@ -256,86 +1367,457 @@ mod tests {
                 database: Box::new(MockDB::new()),
             });
 
-            let page = HomePage { db };
+            let page = HomePage::new(db);
             assert!(page
-                .draw_page()
+                .draw_page(&mut Frame::default())
                 .is_ok());
         }
 
+        #[test]
+        fn draw_page_should_highlight_selected_epic() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            db.create_epic(Epic::new("first".to_owned(), "".to_owned()))
+                .unwrap();
+            db.create_epic(Epic::new("second".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = HomePage::new(db);
+            page.selection
+                .move_by(1, 2);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            let highlighted = frame
+                .highlighted()
+                .unwrap();
+            assert!(frame.lines()[highlighted].contains("second"));
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![1, 2]
+            );
+        }
+
+        #[test]
+        fn in_project_should_list_only_the_project_epics_with_keys() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            db.create_project("API", "backend".to_owned())
+                .unwrap();
+            let keyed = db
+                .create_project_epic("API", Epic::new("gateway".to_owned(), "".to_owned()))
+                .unwrap();
+            let loose = db
+                .create_epic(Epic::new("loose".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = HomePage::in_project(Rc::clone(&db), Some("API".to_owned()), 10);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            let lines = frame.lines();
+            assert!(lines[0].contains("EPICS OF API: backend"));
+            assert!(lines
+                .iter()
+                .any(|line| line.contains("API-1 gateway")));
+            assert!(!lines
+                .iter()
+                .any(|line| line.contains("loose")));
+            assert_eq!(
+                page.handle_input(&loose.to_string())
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input("j")
+                    .unwrap(),
+                Some(Action::NavigateToProjects)
+            );
+            assert_eq!(
+                HomePage::new(db)
+                    .selectable_ids()
+                    .unwrap(),
+                vec![keyed, loose]
+            );
+        }
+
+        #[test]
+        fn handle_input_should_page_and_sort_epics() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            for name in ["charlie", "alpha", "bravo"] {
+                db.create_epic(Epic::new(name.to_owned(), "".to_owned()))
+                    .unwrap();
+            }
+            db.update_epic_priority(3, Priority::High)
+                .unwrap();
+
+            let page = HomePage::with_page_size(db, 2);
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![1, 2]
+            );
+
+            page.selection
+                .move_by(1, 2);
+            assert_eq!(
+                page.handle_input("n")
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![3]
+            );
+            assert_eq!(
+                page.selection
+                    .selected(&[3]),
+                Some(3)
+            );
+
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+            assert!(frame
+                .lines()
+                .iter()
+                .any(|line| line == "page 2/2 | sort: id"));
+
+            // sorting starts over on the first page
+            page.handle_input("s")
+                .unwrap();
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![2, 3]
+            );
+            page.handle_input("n")
+                .unwrap();
+            page.handle_input("p")
+                .unwrap();
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![2, 3]
+            );
+
+            for _ in 0..3 {
+                page.handle_input("s")
+                    .unwrap();
+            }
+            assert_eq!(
+                page.listing
+                    .sort_key(),
+                SortKey::Priority
+            );
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![3, 1]
+            );
+        }
+
+        #[test]
+        fn draw_page_should_tone_status_and_high_priority_rows() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            db.create_epic(Epic::new("normal".to_owned(), "".to_owned()))
+                .unwrap();
+            let urgent_id = db
+                .create_epic(Epic::new("urgent".to_owned(), "".to_owned()))
+                .unwrap();
+            db.update_epic_priority(urgent_id, Priority::High)
+                .unwrap();
+            db.update_epic_status(urgent_id, Status::InProgress)
+                .unwrap();
+
+            let page = HomePage::new(db);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            let row = |name: &str| {
+                frame
+                    .lines()
+                    .iter()
+                    .position(|line| line.contains(name))
+                    .unwrap()
+            };
+            let normal = frame.spans(row("normal"));
+            assert_eq!(normal.len(), 1);
+            assert_eq!(normal[0].tone, Tone::Status(Status::Open));
+            assert_eq!(
+                frame.lines()[row("normal")][normal[0]
+                    .range
+                    .clone()]
+                .trim(),
+                "OPEN"
+            );
+
+            let urgent = frame.spans(row("urgent"));
+            assert_eq!(
+                urgent
+                    .iter()
+                    .map(|span| span.tone)
+                    .collect::<Vec<_>>(),
+                [Tone::Alert, Tone::Status(Status::InProgress)]
+            );
+        }
+
         #[test]
         fn handle_input_should_not_throw_error() {
             let db = Rc::new(JiraHandle {
                 database: Box::new(MockDB::new()),
             });
 
-            let page = HomePage { db };
+            let page = HomePage::new(db);
             assert!(page
                 .handle_input("")
                 .is_ok());
         }
 
         #[test]
-        fn handle_input_should_return_the_correct_actions() {
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+
+            let epic = Epic::new("epic".to_owned(), "".to_owned());
+
+            let epic_id = db
+                .create_epic(epic)
+                .unwrap();
+
+            let page = HomePage::new(db);
+
+            let q = "q";
+            let c = "c";
+            let valid_epic_id = epic_id.to_string();
+            let invalid_epic_id = "999";
+            let junk_input = "j983f2j";
+            let junk_input_with_valid_prefix = "q983f2j";
+            let input_with_trailing_white_spaces = "q\n";
+
+            assert_eq!(
+                page.handle_input(q)
+                    .unwrap(),
+                Some(Action::Exit)
+            );
+            assert_eq!(
+                page.handle_input(c)
+                    .unwrap(),
+                Some(Action::CreateEpic)
+            );
+            assert_eq!(
+                page.handle_input("b")
+                    .unwrap(),
+                Some(Action::NavigateToBacklog)
+            );
+            assert_eq!(
+                page.handle_input("a")
+                    .unwrap(),
+                Some(Action::NavigateToAgenda)
+            );
+            assert_eq!(
+                page.handle_input(&valid_epic_id)
+                    .unwrap(),
+                Some(Action::NavigateToEpicDetail { epic_id: 1 })
+            );
+            assert_eq!(
+                page.handle_input(invalid_epic_id)
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input(junk_input)
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input(junk_input_with_valid_prefix)
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input(input_with_trailing_white_spaces)
+                    .unwrap(),
+                None
+            );
+        }
+    }
+
+    mod epic_detail_page {
+        use super::*;
+
+        #[test]
+        fn handle_input_should_page_back_before_leaving() {
             let db = Rc::new(JiraHandle {
                 database: Box::new(MockDB::new()),
             });
-
-            let epic = Epic::new("".to_owned(), "".to_owned());
-
             let epic_id = db
-                .create_epic(epic)
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
+            for _ in 0..3 {
+                db.create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+                    .unwrap();
+            }
 
-            let page = HomePage { db };
-
-            let q = "q";
-            let c = "c";
-            let valid_epic_id = epic_id.to_string();
-            let invalid_epic_id = "999";
-            let junk_input = "j983f2j";
-            let junk_input_with_valid_prefix = "q983f2j";
-            let input_with_trailing_white_spaces = "q\n";
-
+            let page = EpicDetail::with_page_size(epic_id, db, 2);
             assert_eq!(
-                page.handle_input(q)
+                page.handle_input("n")
                     .unwrap(),
-                Some(Action::Exit)
+                None
             );
             assert_eq!(
-                page.handle_input(c)
+                page.selectable_ids()
                     .unwrap(),
-                Some(Action::CreateEpic)
+                vec![4]
             );
             assert_eq!(
-                page.handle_input(&valid_epic_id)
+                page.handle_input("p")
                     .unwrap(),
-                Some(Action::NavigateToEpicDetail { epic_id: 1 })
+                None
             );
             assert_eq!(
-                page.handle_input(invalid_epic_id)
+                page.selectable_ids()
                     .unwrap(),
-                None
+                vec![2, 3]
             );
             assert_eq!(
-                page.handle_input(junk_input)
+                page.handle_input("p")
                     .unwrap(),
-                None
+                Some(Action::NavigateToPreviousPage)
+            );
+        }
+
+        #[test]
+        fn handle_input_should_filter_stories_by_type() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("feature".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            let bug_id = db
+                .create_story(
+                    Story::new_bug("crash".to_owned(), "".to_owned(), BugDetails::default()),
+                    epic_id,
+                )
+                .unwrap();
+
+            let page = EpicDetail::new(epic_id, db);
+            assert_eq!(
+                page.selectable_ids()
+                    .unwrap(),
+                vec![story_id, bug_id]
             );
+
+            page.handle_input("f")
+                .unwrap();
             assert_eq!(
-                page.handle_input(junk_input_with_valid_prefix)
+                page.selectable_ids()
                     .unwrap(),
-                None
+                vec![story_id]
             );
+
+            page.handle_input("f")
+                .unwrap();
             assert_eq!(
-                page.handle_input(input_with_trailing_white_spaces)
+                page.selectable_ids()
                     .unwrap(),
+                vec![bug_id]
+            );
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+            assert!(frame
+                .lines()
+                .iter()
+                .any(|line| line.contains("BUG") && line.contains("crash")));
+            assert!(frame
+                .lines()
+                .iter()
+                .any(|line| line.ends_with("| type: bug")));
+
+            for _ in 0..3 {
+                page.handle_input("f")
+                    .unwrap();
+            }
+            assert_eq!(
+                page.type_filter
+                    .get(),
                 None
             );
         }
-    }
 
-    mod epic_detail_page {
-        use super::*;
+        #[test]
+        fn draw_page_should_show_subtask_counts_on_story_rows() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            for name in ["one", "two", "three"] {
+                db.create_subtask(SubTask::new(name.to_owned(), None), story_id)
+                    .unwrap();
+            }
+            db.toggle_subtask(story_id + 1)
+                .unwrap();
+
+            let page = EpicDetail::new(epic_id, db);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            assert!(frame
+                .lines()
+                .iter()
+                .any(|line| line.contains("story (1/3 done)")));
+        }
+
+        #[test]
+        fn draw_page_should_show_checklist_progress_on_story_rows() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            db.create_story(
+                Story::new(
+                    "checklist".to_owned(),
+                    "- [x] design\n- [ ] build\n- [ ] ship".to_owned(),
+                ),
+                epic_id,
+            )
+            .unwrap();
+
+            let page = EpicDetail::new(epic_id, db);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            assert!(frame
+                .lines()
+                .iter()
+                .any(|line| line.contains("checklist [1/3]")));
+        }
 
         #[test]
         fn draw_page_should_not_throw_error() {
@@ -343,12 +1825,12 @@ mod tests {
                 database: Box::new(MockDB::new()),
             });
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
 
-            let page = EpicDetail { epic_id, db };
+            let page = EpicDetail::new(epic_id, db);
             assert_eq!(
-                page.draw_page()
+                page.draw_page(&mut Frame::default())
                     .is_ok(),
                 true
             );
@@ -360,10 +1842,10 @@ mod tests {
                 database: Box::new(MockDB::new()),
             });
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
 
-            let page = EpicDetail { epic_id, db };
+            let page = EpicDetail::new(epic_id, db);
             assert_eq!(
                 page.handle_input("")
                     .is_ok(),
@@ -377,9 +1859,9 @@ mod tests {
                 database: Box::new(MockDB::new()),
             });
 
-            let page = EpicDetail { epic_id: 999, db };
+            let page = EpicDetail::new(999, db);
             assert_eq!(
-                page.draw_page()
+                page.draw_page(&mut Frame::default())
                     .is_err(),
                 true
             );
@@ -392,13 +1874,13 @@ mod tests {
             });
 
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
             let story_id = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
 
-            let page = EpicDetail { epic_id, db };
+            let page = EpicDetail::new(epic_id, db);
 
             let p = "p";
             let u = "u";
@@ -419,6 +1901,11 @@ mod tests {
                     .unwrap(),
                 Some(Action::UpdateEpicStatus { epic_id: 1 })
             );
+            assert_eq!(
+                page.handle_input("r")
+                    .unwrap(),
+                Some(Action::UpdateEpicPriority { epic_id: 1 })
+            );
             assert_eq!(
                 page.handle_input(d)
                     .unwrap(),
@@ -460,9 +1947,274 @@ mod tests {
         }
     }
 
+    mod backlog_page {
+        use super::*;
+
+        #[test]
+        fn draw_page_should_list_only_backlog_stories() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            db.create_story(Story::new("attached".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            db.create_backlog_story(Story::new("loose".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = BacklogPage::new(db);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            assert!(frame
+                .lines()
+                .iter()
+                .any(|line| line.contains("loose")));
+            assert!(!frame
+                .lines()
+                .iter()
+                .any(|line| line.contains("attached")));
+        }
+
+        #[test]
+        fn handle_input_should_return_the_correct_actions() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            let attached_id = db
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+            let story_id = db
+                .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = BacklogPage::new(db);
+
+            assert_eq!(
+                page.handle_input("p")
+                    .unwrap(),
+                Some(Action::NavigateToPreviousPage)
+            );
+            assert_eq!(
+                page.handle_input("c")
+                    .unwrap(),
+                Some(Action::CreateBacklogStory)
+            );
+            assert_eq!(
+                page.handle_input("a")
+                    .unwrap(),
+                Some(Action::AttachStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input(&story_id.to_string())
+                    .unwrap(),
+                Some(Action::NavigateToBacklogStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input(&attached_id.to_string())
+                    .unwrap(),
+                None
+            );
+        }
+
+        #[test]
+        fn story_detail_should_delete_backlog_story() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let story_id = db
+                .create_backlog_story(Story::new("story".to_owned(), "".to_owned()))
+                .unwrap();
+
+            let page = StoryDetail::in_backlog(story_id, db);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            assert_eq!(
+                page.handle_input("d")
+                    .unwrap(),
+                Some(Action::DeleteBacklogStory { story_id })
+            );
+        }
+    }
+
     mod story_detail_page {
         use super::*;
 
+        #[test]
+        fn draw_page_should_show_links_and_blocked_flag() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let story_id = db
+                .create_backlog_story(Story::new("deploy".to_owned(), "".to_owned()))
+                .unwrap();
+            let blocker = db
+                .create_backlog_story(Story::new("migrate".to_owned(), "".to_owned()))
+                .unwrap();
+            let related = db
+                .create_backlog_story(Story::new("docs".to_owned(), "".to_owned()))
+                .unwrap();
+            db.link_stories(story_id, LinkKind::IsBlockedBy, blocker)
+                .unwrap();
+            db.link_stories(related, LinkKind::RelatesTo, story_id)
+                .unwrap();
+
+            let page = StoryDetail::in_backlog(story_id, Rc::clone(&db));
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            let lines = frame.lines();
+            assert!(lines[2].contains("deploy (blocked)"));
+            let blocked = lines
+                .iter()
+                .position(|line| line == "blocked by: 2")
+                .unwrap();
+            assert!(frame
+                .spans(blocked)
+                .iter()
+                .any(|span| span.tone == Tone::Alert));
+            let links = lines
+                .iter()
+                .position(|line| line.contains(" LINKS "))
+                .unwrap();
+            assert!(lines[links + 2].starts_with("is blocked by "));
+            assert!(lines[links + 2].contains("| 2 "));
+            assert!(lines[links + 3].starts_with("relates to "));
+            assert!(lines[links + 3].contains("| 3 "));
+
+            assert_eq!(
+                page.handle_input("l")
+                    .unwrap(),
+                Some(Action::LinkStory { story_id })
+            );
+            assert_eq!(
+                page.handle_input("o")
+                    .unwrap(),
+                Some(Action::UnlinkStory { story_id })
+            );
+        }
+
+        #[test]
+        fn draw_page_should_show_bug_details() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(
+                    Story::new_bug(
+                        "crash".to_owned(),
+                        "".to_owned(),
+                        BugDetails {
+                            steps_to_reproduce: "1. open the app".to_owned(),
+                            severity: Severity::Critical,
+                        },
+                    ),
+                    epic_id,
+                )
+                .unwrap();
+
+            let page = StoryDetail::new(epic_id, story_id, db);
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            let lines = frame.lines();
+            assert!(lines
+                .iter()
+                .any(|line| line == "priority: MEDIUM | severity: CRITICAL"));
+            let steps = lines
+                .iter()
+                .position(|line| line.contains(" STEPS TO REPRODUCE "))
+                .unwrap();
+            assert_eq!(lines[steps + 1], "1. open the app");
+        }
+
+        #[test]
+        fn handle_input_should_return_subtask_actions() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            let story_id = db
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+                .unwrap();
+
+            let page = StoryDetail::new(epic_id, story_id, Rc::clone(&db));
+            assert_eq!(
+                page.handle_input("x")
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                page.handle_input("t")
+                    .unwrap(),
+                Some(Action::ToggleTimer { story_id })
+            );
+            assert_eq!(
+                page.handle_input("a")
+                    .unwrap(),
+                Some(Action::CreateSubTask { story_id })
+            );
+
+            let first = db
+                .create_subtask(SubTask::new("first".to_owned(), None), story_id)
+                .unwrap();
+            let second = db
+                .create_subtask(
+                    SubTask::new("second".to_owned(), Some("kim".to_owned())),
+                    story_id,
+                )
+                .unwrap();
+            page.selection
+                .move_by(1, 2);
+
+            assert_eq!(
+                page.handle_input(&second.to_string())
+                    .unwrap(),
+                Some(Action::ToggleSubTask { subtask_id: second })
+            );
+            assert_eq!(
+                page.handle_input("x")
+                    .unwrap(),
+                Some(Action::DeleteSubTask {
+                    story_id,
+                    subtask_id: second
+                })
+            );
+            assert_eq!(
+                page.handle_input(&first.to_string())
+                    .unwrap(),
+                Some(Action::ToggleSubTask { subtask_id: first })
+            );
+            assert_eq!(
+                page.handle_input(&story_id.to_string())
+                    .unwrap(),
+                None
+            );
+
+            let mut frame = Frame::default();
+            page.draw_page(&mut frame)
+                .unwrap();
+            let highlighted = frame
+                .highlighted()
+                .unwrap();
+            assert!(frame.lines()[highlighted].contains("second"));
+            assert!(frame.lines()[highlighted].contains("kim"));
+        }
+
         #[test]
         fn draw_page_should_not_throw_error() {
             let db = Rc::new(JiraHandle {
@@ -470,19 +2222,15 @@ mod tests {
             });
 
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
             let story_id = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id,
-                db,
-            };
+            let page = StoryDetail::new(epic_id, story_id, db);
             assert_eq!(
-                page.draw_page()
+                page.draw_page(&mut Frame::default())
                     .is_ok(),
                 true
             );
@@ -495,17 +2243,13 @@ mod tests {
             });
 
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
             let story_id = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id,
-                db,
-            };
+            let page = StoryDetail::new(epic_id, story_id, db);
             assert_eq!(
                 page.handle_input("")
                     .is_ok(),
@@ -513,6 +2257,40 @@ mod tests {
             );
         }
 
+        #[test]
+        fn draw_page_should_fit_and_wrap_to_frame_width() {
+            let db = Rc::new(JiraHandle {
+                database: Box::new(MockDB::new()),
+            });
+
+            let epic_id = db
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+                .unwrap();
+            let description = "a long description that does not fit in a single narrow column";
+            let story_id = db
+                .create_story(
+                    Story::new("name".to_owned(), description.to_owned()),
+                    epic_id,
+                )
+                .unwrap();
+
+            let page = StoryDetail::new(epic_id, story_id, db);
+            let mut frame = Frame::new(50);
+            page.draw_page(&mut frame)
+                .unwrap();
+
+            // the key hints are clipped by the tui rather than wrapped
+            assert!(frame
+                .lines()
+                .iter()
+                .filter(|line| !line.starts_with('['))
+                .all(|line| display_width(line) <= 50));
+            let text = frame
+                .lines()
+                .join(" ");
+            assert!(text.contains("narrow"));
+        }
+
         #[test]
         fn draw_page_should_throw_error_for_invalid_story_id() {
             let db = Rc::new(JiraHandle {
@@ -520,19 +2298,15 @@ mod tests {
             });
 
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
             let _ = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id: 999,
-                db,
-            };
+            let page = StoryDetail::new(epic_id, 999, db);
             assert_eq!(
-                page.draw_page()
+                page.draw_page(&mut Frame::default())
                     .is_err(),
                 true
             );
@@ -545,17 +2319,13 @@ mod tests {
             });
 
             let epic_id = db
-                .create_epic(Epic::new("".to_owned(), "".to_owned()))
+                .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
                 .unwrap();
             let story_id = db
-                .create_story(Story::new("".to_owned(), "".to_owned()), epic_id)
+                .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
                 .unwrap();
 
-            let page = StoryDetail {
-                epic_id,
-                story_id,
-                db,
-            };
+            let page = StoryDetail::new(epic_id, story_id, db);
 
             let p = "p";
             let u = "u";
@@ -575,6 +2345,11 @@ mod tests {
                     .unwrap(),
                 Some(Action::UpdateStoryStatus { story_id })
             );
+            assert_eq!(
+                page.handle_input("r")
+                    .unwrap(),
+                Some(Action::UpdateStoryPriority { story_id })
+            );
             assert_eq!(
                 page.handle_input(d)
                     .unwrap(),
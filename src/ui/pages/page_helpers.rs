@@ -1,40 +1,705 @@
-use ellipse::Ellipse;
+use std::cell::Cell;
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::models::Status;
+
+// width the original fixed-width tables were laid out for
+pub const DEFAULT_WIDTH: usize = 66;
+
+// what a piece of text means rather than how it looks; the tui picks the
+// colors from the configured theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    Status(Status),
+    // rows that need attention, e.g. high priority items
+    Alert,
+    Heading,
+    Code,
+}
+
+// tone for a byte range of a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub range: Range<usize>,
+    pub tone: Tone,
+}
+
+// pages render into a frame instead of stdout so the tui can place, clip and
+// highlight lines without the pages knowing about the terminal
+pub struct Frame {
+    width: usize,
+    lines: Vec<String>,
+    // spans per line, later spans take precedence over earlier ones
+    spans: Vec<Vec<Span>>,
+    highlighted: Option<usize>,
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Frame::new(DEFAULT_WIDTH)
+    }
+}
+
+impl Frame {
+    pub fn new(width: usize) -> Self {
+        Frame {
+            width,
+            lines: vec![],
+            spans: vec![],
+            highlighted: None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    // section heading centered in a dashed rule across the frame
+    pub fn title(&mut self, title: &str) {
+        let title = format!(" {} ", title);
+        let dashes = self
+            .width
+            .saturating_sub(display_width(&title))
+            .max(2);
+        let left = dashes / 2;
+        self.line(format!(
+            "{}{}{}",
+            "-".repeat(left),
+            title,
+            "-".repeat(dashes - left)
+        ));
+    }
+
+    pub fn line(&mut self, text: impl Into<String>) {
+        self.lines
+            .push(text.into());
+        self.spans
+            .push(vec![]);
+    }
+
+    pub fn row(&mut self, text: impl Into<String>, highlighted: bool) {
+        if highlighted {
+            self.highlighted = Some(
+                self.lines
+                    .len(),
+            );
+        }
+        self.line(text);
+    }
+
+    // tones part of the line added last
+    pub fn tone_range(&mut self, range: Range<usize>, tone: Tone) {
+        if let Some(spans) = self
+            .spans
+            .last_mut()
+        {
+            spans.push(Span { range, tone });
+        }
+    }
+
+    // tones the whole line added last
+    pub fn tone_line(&mut self, tone: Tone) {
+        let len = self
+            .lines
+            .last()
+            .map_or(0, String::len);
+        self.tone_range(0..len, tone);
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn spans(&self, line: usize) -> &[Span] {
+        self.spans
+            .get(line)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+}
+
+pub struct Column {
+    pub title: &'static str,
+    // share of the frame width relative to the other columns
+    pub weight: usize,
+    pub min_width: usize,
+    // wrap onto extra lines instead of truncating
+    pub wrap: bool,
+}
+
+impl Column {
+    pub fn new(title: &'static str, weight: usize, min_width: usize) -> Self {
+        Column {
+            title,
+            weight,
+            min_width,
+            wrap: false,
+        }
+    }
+
+    pub fn wrapped(self) -> Self {
+        Column { wrap: true, ..self }
+    }
+}
+
+const SEPARATOR: &str = " | ";
+
+pub struct Table {
+    columns: Vec<Column>,
+    widths: Vec<usize>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>, total_width: usize) -> Self {
+        let widths = distribute_widths(&columns, total_width);
+        Table { columns, widths }
+    }
+
+    #[cfg(test)]
+    pub fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    pub fn header(&self) -> String {
+        self.columns
+            .iter()
+            .zip(&self.widths)
+            .map(|(column, width)| {
+                get_column_string(&format!("{:^width$}", column.title, width = width), *width)
+            })
+            .collect::<Vec<String>>()
+            .join(SEPARATOR)
+    }
+
+    // byte range the given column takes up in a line produced by `row`
+    pub fn cell_range(&self, line: &str, column: usize) -> Range<usize> {
+        let start = self.widths[..column]
+            .iter()
+            .sum::<usize>()
+            + SEPARATOR.len() * column;
+        let end = start + self.widths[column];
+        byte_offset(line, start)..byte_offset(line, end)
+    }
+
+    // one line per row unless a wrapping column needs more
+    pub fn row(&self, cells: &[&str]) -> Vec<String> {
+        let cell_lines: Vec<Vec<String>> = self
+            .columns
+            .iter()
+            .zip(&self.widths)
+            .zip(cells)
+            .map(|((column, width), cell)| {
+                if column.wrap {
+                    wrap_text(cell, *width)
+                } else {
+                    vec![cell.to_string()]
+                }
+            })
+            .collect();
+        let height = cell_lines
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(1);
+
+        (0..height)
+            .map(|line| {
+                cell_lines
+                    .iter()
+                    .zip(&self.widths)
+                    .map(|(lines, width)| {
+                        let text = lines
+                            .get(line)
+                            .map(String::as_str)
+                            .unwrap_or("");
+                        get_column_string(text, *width)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(SEPARATOR)
+            })
+            .collect()
+    }
+}
+
+// every column gets its minimum and the rest of the width is shared by
+// weight; the remainder from rounding goes to the widest column
+fn distribute_widths(columns: &[Column], total_width: usize) -> Vec<usize> {
+    let separators = SEPARATOR.len()
+        * columns
+            .len()
+            .saturating_sub(1);
+    let minimum: usize = columns
+        .iter()
+        .map(|column| column.min_width)
+        .sum();
+    let spare = total_width.saturating_sub(separators + minimum);
+    let total_weight: usize = columns
+        .iter()
+        .map(|column| column.weight)
+        .sum::<usize>()
+        .max(1);
+
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .map(|column| column.min_width + spare * column.weight / total_weight)
+        .collect();
+
+    let used: usize = widths
+        .iter()
+        .sum();
+    let remainder = total_width
+        .saturating_sub(separators)
+        .saturating_sub(used);
+    if let Some((widest, _)) = columns
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, column)| column.weight)
+    {
+        if used >= minimum && remainder > 0 {
+            widths[widest] += remainder;
+        }
+    }
+    widths
+}
+
+// cursor over the selectable rows of a page; pages are shared immutably by
+// the navigator so the position lives in a cell
+#[derive(Default)]
+pub struct Selection {
+    cursor: Cell<usize>,
+}
+
+impl Selection {
+    pub fn move_by(&self, delta: isize, len: usize) {
+        if len == 0 {
+            self.cursor
+                .set(0);
+            return;
+        }
+        let cursor = (self
+            .cursor
+            .get() as isize
+            + delta)
+            .clamp(0, len as isize - 1);
+        self.cursor
+            .set(cursor as usize);
+    }
+
+    pub fn reset(&self) {
+        self.cursor
+            .set(0);
+    }
+
+    pub fn selected(&self, ids: &[u32]) -> Option<u32> {
+        if ids.is_empty() {
+            return None;
+        }
+        let cursor = self
+            .cursor
+            .get()
+            .min(ids.len() - 1);
+        Some(ids[cursor])
+    }
+}
+
+// number of terminal cells the text takes up; wide characters such as CJK
+// take two cells and combining marks none
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+// longest prefix that fits in `width` cells without splitting a grapheme,
+// along with the width it actually takes up
+pub fn truncate_to_width(text: &str, width: usize) -> (&str, usize) {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        let grapheme_width = grapheme.width();
+        if used + grapheme_width > width {
+            return (&text[..index], used);
+        }
+        used += grapheme_width;
+    }
+    (text, used)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Id,
+    Name,
+    Status,
+    Created,
+    Priority,
+}
+
+impl SortKey {
+    // order the footer toggle cycles through
+    pub fn next(self) -> SortKey {
+        match self {
+            SortKey::Id => SortKey::Name,
+            SortKey::Name => SortKey::Status,
+            SortKey::Status => SortKey::Created,
+            SortKey::Created => SortKey::Priority,
+            SortKey::Priority => SortKey::Id,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Id => "id",
+            SortKey::Name => "name",
+            SortKey::Status => "status",
+            SortKey::Created => "created",
+            SortKey::Priority => "priority",
+        }
+    }
+}
+
+// paging and sort order of a list page; like the selection it lives in cells
+// because pages are shared immutably
+pub struct Listing {
+    page: Cell<usize>,
+    sort: Cell<SortKey>,
+    page_size: usize,
+}
+
+impl Listing {
+    pub fn new(page_size: usize) -> Self {
+        Listing {
+            page: Cell::new(0),
+            sort: Cell::new(SortKey::default()),
+            page_size: page_size.max(1),
+        }
+    }
+
+    pub fn sort_key(&self) -> SortKey {
+        self.sort
+            .get()
+    }
+
+    pub fn cycle_sort(&self) {
+        self.sort
+            .set(
+                self.sort
+                    .get()
+                    .next(),
+            );
+        self.first_page();
+    }
+
+    fn page_count(&self, len: usize) -> usize {
+        len.div_ceil(self.page_size)
+            .max(1)
+    }
+
+    // current page, clamped in case items were deleted since it was chosen
+    fn page(&self, len: usize) -> usize {
+        self.page
+            .get()
+            .min(self.page_count(len) - 1)
+    }
+
+    pub fn first_page(&self) {
+        self.page
+            .set(0);
+    }
+
+    // false when already on the last page
+    pub fn next_page(&self, len: usize) -> bool {
+        let page = self.page(len);
+        if page + 1 >= self.page_count(len) {
+            return false;
+        }
+        self.page
+            .set(page + 1);
+        true
+    }
+
+    // false when already on the first page
+    pub fn previous_page(&self, len: usize) -> bool {
+        let page = self.page(len);
+        if page == 0 {
+            return false;
+        }
+        self.page
+            .set(page - 1);
+        true
+    }
+
+    pub fn visible<'a>(&self, ids: &'a [u32]) -> &'a [u32] {
+        let start = self.page(ids.len()) * self.page_size;
+        let end = (start + self.page_size).min(ids.len());
+        &ids[start..end]
+    }
+
+    pub fn summary(&self, len: usize) -> String {
+        format!(
+            "page {}/{} | sort: {}",
+            self.page(len) + 1,
+            self.page_count(len),
+            self.sort_key()
+                .label()
+        )
+    }
+}
 
 pub fn get_column_string(text: &str, width: usize) -> String {
-    let len = text.len();
+    let text_width = display_width(text);
 
-    match len.cmp(&width) {
-        std::cmp::Ordering::Equal => text.to_owned(),
-        std::cmp::Ordering::Less => {
-            let left_over = width - len;
-            let mut column_string = text.to_owned();
+    if text_width <= width {
+        return format!("{}{}", text, " ".repeat(width - text_width));
+    }
+    if width <= 3 {
+        return ".".repeat(width);
+    }
 
-            for _ in 0..left_over {
-                column_string.push(' ');
-            }
+    // a wide character that does not fit leaves a gap, which is padded so
+    // the column still lines up
+    let (truncated, used) = truncate_to_width(text, width - 3);
+    format!("{}...{}", truncated, " ".repeat(width - 3 - used))
+}
+
+// byte offset of the first grapheme starting at or after `cells` columns
+fn byte_offset(text: &str, cells: usize) -> usize {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        if used >= cells {
+            return index;
+        }
+        used += grapheme.width();
+    }
+    text.len()
+}
+
+// greedy word wrap by display width; words wider than the line are split
+// between graphemes
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = vec![];
+    let mut current = String::new();
 
-            column_string
-        },
-        std::cmp::Ordering::Greater => {
-            if width == 0 {
-                return "".to_owned();
-            } else if width == 1 {
-                return ".".to_owned();
-            } else if width == 2 {
-                return "..".to_owned();
-            } else if width == 3 {
-                return "...".to_owned();
+    for word in text.split_whitespace() {
+        let mut word = word;
+        while display_width(word) > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
             }
-            let result = text.truncate_ellipse(width-3);
-            result.to_string()
-        },
+            let (head, _) = truncate_to_width(word, width);
+            // always make progress, even if a single grapheme is too wide
+            let head = if head.is_empty() {
+                word.graphemes(true)
+                    .next()
+                    .unwrap_or(word)
+            } else {
+                head
+            };
+            lines.push(head.to_owned());
+            word = &word[head.len()..];
+        }
+
+        let current_width = display_width(&current);
+        if current_width > 0 && current_width + 1 + display_width(word) > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
     }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn frame_should_track_highlighted_row() {
+        let mut frame = Frame::default();
+        frame.line("header");
+        frame.row("row 1", false);
+        frame.row("row 2", true);
+
+        assert_eq!(frame.lines(), ["header", "row 1", "row 2"]);
+        assert_eq!(frame.highlighted(), Some(2));
+    }
+
+    #[test]
+    fn frame_should_keep_spans_per_line() {
+        let mut frame = Frame::default();
+        frame.line("header");
+        frame.line("row");
+        frame.tone_line(Tone::Alert);
+        frame.tone_range(1..2, Tone::Status(Status::Open));
+
+        assert!(frame
+            .spans(0)
+            .is_empty());
+        assert_eq!(
+            frame.spans(1),
+            [
+                Span {
+                    range: 0..3,
+                    tone: Tone::Alert
+                },
+                Span {
+                    range: 1..2,
+                    tone: Tone::Status(Status::Open)
+                }
+            ]
+        );
+        assert!(frame
+            .spans(2)
+            .is_empty());
+    }
+
+    #[test]
+    fn table_cell_range_should_map_columns_to_bytes() {
+        let table = Table::new(
+            vec![Column::new("name", 0, 4), Column::new("status", 0, 4)],
+            11,
+        );
+        let line = table.row(&["日本", "OPEN"])[0].clone();
+
+        assert_eq!(line, "日本 | OPEN");
+        assert_eq!(&line[table.cell_range(&line, 0)], "日本");
+        assert_eq!(&line[table.cell_range(&line, 1)], "OPEN");
+    }
+
+    #[test]
+    fn selection_should_stay_within_bounds() {
+        let selection = Selection::default();
+        let ids = [3, 5, 8];
+
+        assert_eq!(selection.selected(&ids), Some(3));
+        selection.move_by(-1, ids.len());
+        assert_eq!(selection.selected(&ids), Some(3));
+        selection.move_by(5, ids.len());
+        assert_eq!(selection.selected(&ids), Some(8));
+        assert_eq!(selection.selected(&ids[..1]), Some(3));
+        assert_eq!(selection.selected(&[]), None);
+    }
+
+    #[test]
+    fn listing_should_page_through_ids() {
+        let listing = Listing::new(2);
+        let ids = [1, 2, 3, 4, 5];
+
+        assert_eq!(listing.visible(&ids), [1, 2]);
+        assert!(!listing.previous_page(ids.len()));
+        assert!(listing.next_page(ids.len()));
+        assert!(listing.next_page(ids.len()));
+        assert_eq!(listing.visible(&ids), [5]);
+        assert_eq!(listing.summary(ids.len()), "page 3/3 | sort: id");
+        assert!(!listing.next_page(ids.len()));
+
+        // shrinking the list keeps the page in range
+        assert_eq!(listing.visible(&ids[..3]), [3]);
+        assert_eq!(listing.visible(&[]), [] as [u32; 0]);
+        assert_eq!(listing.summary(0), "page 1/1 | sort: id");
+
+        listing.cycle_sort();
+        assert_eq!(listing.sort_key(), SortKey::Name);
+        assert_eq!(listing.visible(&ids), [1, 2]);
+    }
+
+    #[test]
+    fn frame_title_should_span_the_width() {
+        let mut frame = Frame::new(20);
+        frame.title("EPICS");
+
+        assert_eq!(frame.lines()[0], "------ EPICS -------");
+    }
+
+    #[test]
+    fn table_should_keep_classic_widths_at_default_width() {
+        let table = Table::new(
+            vec![
+                Column::new("id", 11, 0),
+                Column::new("name", 32, 0),
+                Column::new("status", 17, 0),
+            ],
+            DEFAULT_WIDTH,
+        );
+
+        assert_eq!(table.widths(), [11, 32, 17]);
+    }
+
+    #[test]
+    fn table_should_scale_columns_to_the_width() {
+        let columns = || vec![Column::new("id", 1, 4), Column::new("name", 3, 4)];
+
+        assert_eq!(Table::new(columns(), 23).widths(), [7, 13]);
+        assert_eq!(Table::new(columns(), 5).widths(), [4, 4]);
+    }
+
+    #[test]
+    fn table_row_should_wrap_wrapped_columns() {
+        let table = Table::new(
+            vec![
+                Column::new("id", 0, 2),
+                Column::new("description", 0, 11).wrapped(),
+            ],
+            16,
+        );
+
+        assert_eq!(table.header(), "id | description");
+        assert_eq!(
+            table.row(&["1", "wrap this long text"]),
+            ["1  | wrap this  ", "   | long text  "]
+        );
+    }
+
+    #[test]
+    fn wrap_text_should_break_on_words() {
+        assert_eq!(wrap_text("", 10), [""]);
+        assert_eq!(wrap_text("one two three", 7), ["one two", "three"]);
+        assert_eq!(wrap_text("abcdefghij k", 4), ["abcd", "efgh", "ij k"]);
+    }
+
+    #[test]
+    fn display_width_should_count_terminal_cells() {
+        assert_eq!(display_width("cafe\u{301}"), 4);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("ok 👍"), 5);
+    }
+
+    #[test]
+    fn get_column_string_should_pad_by_display_width() {
+        assert_eq!(get_column_string("café", 6), "café  ");
+        assert_eq!(get_column_string("cafe\u{301}", 6), "cafe\u{301}  ");
+        assert_eq!(get_column_string("日本", 6), "日本  ");
+        assert_eq!(get_column_string("👍", 3), "👍 ");
+    }
+
+    #[test]
+    fn get_column_string_should_not_split_graphemes() {
+        // the combining accent stays with its letter
+        assert_eq!(get_column_string("cafe\u{301}s", 7), "cafe\u{301}s  ");
+        assert_eq!(get_column_string("cafe\u{301}teria", 7), "cafe\u{301}...");
+        // a wide character that would straddle the edge is dropped and padded
+        assert_eq!(get_column_string("日本語です", 8), "日本... ");
+        assert_eq!(get_column_string("👨‍👩‍👧 family", 6), "👨‍👩‍👧 ...");
+        assert_eq!(display_width(&get_column_string("mixed 日本 👍 é", 9)), 9);
+    }
+
+    #[test]
+    fn wrap_text_should_wrap_by_display_width() {
+        assert_eq!(wrap_text("日本語 です", 6), ["日本語", "です"]);
+        assert_eq!(wrap_text("日本語です", 4), ["日本", "語で", "す"]);
+        assert_eq!(wrap_text("naïve café", 5), ["naïve", "café"]);
+    }
+
     #[test]
     fn test_get_column_string() {
         let text1 = "";
@@ -68,5 +733,5 @@ mod tests {
         assert_eq!(get_column_string(text2, width), "test  ".to_owned());
         assert_eq!(get_column_string(text3, width), "testme".to_owned());
         assert_eq!(get_column_string(text4, width), "tes...".to_owned());
-    } 
-}
\ No newline at end of file
+    }
+}
@@ -0,0 +1,221 @@
+use std::any::Any;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use super::page_helpers::{Column, Frame, Selection, Table};
+use super::Page;
+use crate::db::JiraHandle;
+use crate::models::{Action, DBState, ItemKey};
+
+// # | key | name | epics | stories listing of projects
+fn project_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("#", 5, 3),
+            Column::new("key", 10, 6),
+            Column::new("name", 30, 8),
+            Column::new("epics", 8, 6),
+            Column::new("stories", 8, 8),
+        ],
+        width,
+    )
+}
+
+fn count_keyed<'a>(keys: impl Iterator<Item = &'a Option<ItemKey>>, project: &str) -> usize {
+    keys.filter(|key| {
+        key.as_ref()
+            .is_some_and(|key| key.project == project)
+    })
+    .count()
+}
+
+// the projects by key, above the epics of each; projects are numbered by
+// row since their keys are not ids
+pub struct ProjectsPage {
+    pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+}
+
+impl ProjectsPage {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        ProjectsPage {
+            db,
+            selection: Selection::default(),
+        }
+    }
+
+    // project key of the row `number`, counted from 1
+    fn project_key(db_state: &DBState, number: u32) -> Option<String> {
+        let index = (number as usize).checked_sub(1)?;
+        db_state
+            .projects
+            .keys()
+            .nth(index)
+            .cloned()
+    }
+}
+
+impl Page for ProjectsPage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let table = project_table(frame.width());
+        frame.title("PROJECTS");
+        frame.line(table.header());
+
+        let ids = self.selectable_ids()?;
+        let selected = self
+            .selection
+            .selected(&ids);
+        for (number, (key, project)) in ids
+            .iter()
+            .zip(
+                db_state
+                    .projects
+                    .iter(),
+            )
+        {
+            let epics = count_keyed(
+                db_state
+                    .epics
+                    .values()
+                    .map(|epic| &epic.key),
+                key,
+            );
+            let stories = count_keyed(
+                db_state
+                    .stories
+                    .values()
+                    .map(|story| &story.key),
+                key,
+            );
+            for (i, line) in table
+                .row(&[
+                    &number.to_string(),
+                    key,
+                    &project.name,
+                    &epics.to_string(),
+                    &stories.to_string(),
+                ])
+                .into_iter()
+                .enumerate()
+            {
+                frame.row(line, selected == Some(*number) && i == 0);
+            }
+        }
+
+        if ids.is_empty() {
+            frame.line("");
+            frame.line("no projects yet");
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[q] quit | [c] create project | [a] all epics | [enter] or [:#:] open project");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "q" => Ok(Some(Action::Exit)),
+            "c" => Ok(Some(Action::CreateProject)),
+            "a" => Ok(Some(Action::NavigateToProject { key: None })),
+            input => {
+                if let Ok(number) = input.parse::<u32>() {
+                    let db_state = self
+                        .db
+                        .read_full_record()?;
+                    if let Some(key) = ProjectsPage::project_key(&db_state, number) {
+                        return Ok(Some(Action::NavigateToProject { key: Some(key) }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        let count = self
+            .db
+            .read_full_record()?
+            .projects
+            .len() as u32;
+        Ok((1..=count).collect())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use crate::models::{Epic, Story};
+
+    #[test]
+    fn projects_page_should_list_projects_and_open_them_by_number() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        db.create_project("WEB", "frontend".to_owned())
+            .unwrap();
+        db.create_project("API", "backend".to_owned())
+            .unwrap();
+        let epic_id = db
+            .create_project_epic("API", Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        db.create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+
+        let page = ProjectsPage::new(db);
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+
+        let lines = frame.lines();
+        let api = lines
+            .iter()
+            .position(|line| line.contains("| API "))
+            .unwrap();
+        let web = lines
+            .iter()
+            .position(|line| line.contains("| WEB "))
+            .unwrap();
+        assert!(api < web);
+        assert!(lines[api].starts_with("1 "));
+        assert!(lines[api].contains("backend"));
+        assert!(lines[api].contains("| 1 "));
+        assert_eq!(
+            page.selectable_ids()
+                .unwrap(),
+            [1, 2]
+        );
+        assert_eq!(
+            page.handle_input("2")
+                .unwrap(),
+            Some(Action::NavigateToProject {
+                key: Some("WEB".to_owned())
+            })
+        );
+        assert_eq!(
+            page.handle_input("3")
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            page.handle_input("a")
+                .unwrap(),
+            Some(Action::NavigateToProject { key: None })
+        );
+    }
+}
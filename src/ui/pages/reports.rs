@@ -0,0 +1,143 @@
+use std::any::Any;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use super::page_helpers::{Column, Frame, Table};
+use super::Page;
+use crate::db::JiraHandle;
+use crate::models::Action;
+use crate::reports::{format_minutes, time_by_epic, time_by_user};
+
+// name | time listing shared by both reports
+fn time_table(title: &'static str, width: usize) -> Table {
+    Table::new(
+        vec![Column::new(title, 40, 8), Column::new("time", 10, 9)],
+        width,
+    )
+}
+
+// logged time per epic for billing, and per user
+pub struct TimeReportPage {
+    pub db: Rc<JiraHandle>,
+}
+
+impl TimeReportPage {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        TimeReportPage { db }
+    }
+}
+
+impl Page for TimeReportPage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+
+        let epics = time_by_epic(&db_state);
+        let table = time_table("epic", frame.width());
+        frame.title("TIME BY EPIC");
+        frame.line(table.header());
+        for row in epics.iter() {
+            let name = match row.epic_id {
+                Some(id) => format!("{}: {}", id, row.name),
+                None => row
+                    .name
+                    .clone(),
+            };
+            for line in table.row(&[&name, &format_minutes(row.minutes)]) {
+                frame.line(line);
+            }
+        }
+
+        frame.line("");
+        let users = time_by_user(&db_state);
+        let table = time_table("user", frame.width());
+        frame.title("TIME BY USER");
+        frame.line(table.header());
+        for row in users.iter() {
+            for line in table.row(&[&row.user, &format_minutes(row.minutes)]) {
+                frame.line(line);
+            }
+        }
+
+        if users.is_empty() {
+            frame.line("");
+            frame.line("no time logged yet");
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[p] previous");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            _ => Ok(None),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use crate::models::{Epic, Story, Worklog};
+    use chrono::Utc;
+
+    #[test]
+    fn draw_page_should_list_time_per_epic_and_user() {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("client a".to_owned(), "".to_owned()))
+            .unwrap();
+        let story_id = db
+            .create_story(Story::new("story".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let mut db_state = db
+            .read_full_record()
+            .unwrap();
+        db_state
+            .stories
+            .get_mut(&story_id)
+            .unwrap()
+            .worklogs
+            .push(Worklog {
+                user: "alice".to_owned(),
+                start: Utc::now(),
+                minutes: 90,
+                note: "".to_owned(),
+            });
+        db.database
+            .write_db(&db_state)
+            .unwrap();
+
+        let page = TimeReportPage::new(db);
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+
+        let lines = frame.lines();
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("1: client a ")));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("alice ") && line.contains(" | 1h 30m")));
+        assert_eq!(
+            page.handle_input("p")
+                .unwrap(),
+            Some(Action::NavigateToPreviousPage)
+        );
+    }
+}
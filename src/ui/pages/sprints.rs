@@ -0,0 +1,602 @@
+use std::any::Any;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+
+use super::page_helpers::{Column, Frame, Selection, Table};
+use super::{navigate_to_story, story_row, story_table, Page};
+use crate::db::JiraHandle;
+use crate::models::{Action, DBState, Sprint, SprintState, Status};
+
+// id | name | dates | state listing of sprints
+fn sprint_table(width: usize) -> Table {
+    Table::new(
+        vec![
+            Column::new("id", 11, 4),
+            Column::new("name", 26, 8),
+            Column::new("dates", 23, 23),
+            Column::new("state", 13, 9),
+        ],
+        width,
+    )
+}
+
+fn sprint_dates(sprint: &Sprint) -> String {
+    let date =
+        |date: Option<chrono::NaiveDate>| date.map_or("?".to_owned(), |date| date.to_string());
+    format!("{} - {}", date(sprint.start), date(sprint.end))
+}
+
+fn sprint_header(frame: &mut Frame, sprint_id: u32, sprint: &Sprint) {
+    let table = sprint_table(frame.width());
+    frame.line(table.header());
+    for line in table.row(&[
+        &sprint_id.to_string(),
+        &sprint.name,
+        &sprint_dates(sprint),
+        &sprint
+            .state
+            .to_string(),
+    ]) {
+        frame.line(line);
+    }
+    if !sprint
+        .goal
+        .is_empty()
+    {
+        frame.line(format!("goal: {}", sprint.goal));
+    }
+}
+
+// stories that can still be pulled into a sprint: not done and not planned
+// into another open sprint
+fn candidate_ids(db_state: &DBState) -> Vec<u32> {
+    let mut ids: Vec<u32> = db_state
+        .stories
+        .iter()
+        .filter(|(id, story)| {
+            !story
+                .status
+                .is_done()
+                && db_state
+                    .open_sprint_of_story(**id)
+                    .is_none()
+        })
+        .map(|(id, _)| *id)
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+pub struct SprintsPage {
+    pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+}
+
+impl SprintsPage {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        SprintsPage {
+            db,
+            selection: Selection::default(),
+        }
+    }
+
+    // by start date, sprints without one last
+    fn sorted_ids(&self) -> Result<Vec<u32>> {
+        let sprints = self
+            .db
+            .read_full_record()?
+            .sprints;
+        let mut ids: Vec<u32> = sprints
+            .keys()
+            .copied()
+            .collect();
+        ids.sort_by_key(|id| {
+            let start = sprints[id].start;
+            (start.is_none(), start, *id)
+        });
+        Ok(ids)
+    }
+}
+
+impl Page for SprintsPage {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let sprints = self
+            .db
+            .read_full_record()?
+            .sprints;
+        let table = sprint_table(frame.width());
+        frame.title("SPRINTS");
+        frame.line(table.header());
+
+        let ids = self.sorted_ids()?;
+        let selected = self
+            .selection
+            .selected(&ids);
+        for id in ids.iter() {
+            let sprint = &sprints[id];
+            for (i, line) in table
+                .row(&[
+                    &id.to_string(),
+                    &sprint.name,
+                    &sprint_dates(sprint),
+                    &sprint
+                        .state
+                        .to_string(),
+                ])
+                .into_iter()
+                .enumerate()
+            {
+                frame.row(line, selected == Some(*id) && i == 0);
+            }
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line(
+            "[p] previous | [c] create sprint | [a] active sprint | [enter] or [:id:] plan sprint",
+        );
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "c" => Ok(Some(Action::CreateSprint)),
+            "a" => Ok(Some(Action::NavigateToActiveSprint)),
+            input => {
+                if let Ok(sprint_id) = input.parse::<u32>() {
+                    if self
+                        .sorted_ids()?
+                        .contains(&sprint_id)
+                    {
+                        return Ok(Some(Action::NavigateToSprintPlanning { sprint_id }));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        self.sorted_ids()
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// the stories of a sprint followed by the ones that can be pulled in;
+// choosing a story moves it to the other list
+pub struct SprintPlanning {
+    pub sprint_id: u32,
+    pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+}
+
+impl SprintPlanning {
+    pub fn new(sprint_id: u32, db: Rc<JiraHandle>) -> Self {
+        SprintPlanning {
+            sprint_id,
+            db,
+            selection: Selection::default(),
+        }
+    }
+
+    // (planned, candidates); completed sprints take no more stories
+    fn story_ids(&self, db_state: &DBState) -> Result<(Vec<u32>, Vec<u32>)> {
+        let sprint = db_state
+            .sprints
+            .get(&self.sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint!"))?;
+        let planned = sprint
+            .stories
+            .iter()
+            .copied()
+            .filter(|id| {
+                db_state
+                    .stories
+                    .contains_key(id)
+            })
+            .collect();
+        let candidates = match sprint.state {
+            SprintState::Completed => vec![],
+            _ => candidate_ids(db_state),
+        };
+        Ok((planned, candidates))
+    }
+}
+
+impl Page for SprintPlanning {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let sprint = db_state
+            .sprints
+            .get(&self.sprint_id)
+            .ok_or_else(|| anyhow!("could not find sprint!"))?;
+        let (planned, candidates) = self.story_ids(&db_state)?;
+        let selected = self
+            .selection
+            .selected(&[planned.as_slice(), candidates.as_slice()].concat());
+
+        frame.title("SPRINT");
+        sprint_header(frame, self.sprint_id, sprint);
+
+        let mut sections = vec![("PLANNED", &planned)];
+        if sprint.state != SprintState::Completed {
+            sections.push(("CANDIDATES", &candidates));
+        }
+        let table = story_table(frame.width());
+        for (title, ids) in sections {
+            frame.line("");
+            frame.title(title);
+            frame.line(table.header());
+            for id in ids.iter() {
+                story_row(
+                    frame,
+                    &table,
+                    *id,
+                    &db_state.stories[id],
+                    &db_state,
+                    selected == Some(*id),
+                );
+            }
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[p] previous | [s] start sprint | [g] burndown | [enter] or [:id:] add or remove story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let (planned, candidates) = self.story_ids(&db_state)?;
+        let sprint_id = self.sprint_id;
+
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "s" => Ok(Some(Action::StartSprint { sprint_id })),
+            "g" => Ok(Some(Action::NavigateToSprintCharts { sprint_id })),
+            input => {
+                let state = db_state.sprints[&sprint_id].state;
+                match input.parse::<u32>() {
+                    Ok(story_id) if state == SprintState::Completed => Ok(planned
+                        .contains(&story_id)
+                        .then(|| navigate_to_story(&db_state, story_id))),
+                    Ok(story_id) if planned.contains(&story_id) => {
+                        Ok(Some(Action::RemoveStoryFromSprint {
+                            sprint_id,
+                            story_id,
+                        }))
+                    }
+                    Ok(story_id) if candidates.contains(&story_id) => {
+                        Ok(Some(Action::AddStoryToSprint {
+                            sprint_id,
+                            story_id,
+                        }))
+                    }
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let (planned, candidates) = self.story_ids(&db_state)?;
+        Ok([planned, candidates].concat())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// board of the running sprint with its stories grouped by status
+pub struct ActiveSprint {
+    pub db: Rc<JiraHandle>,
+    pub selection: Selection,
+}
+
+impl ActiveSprint {
+    pub fn new(db: Rc<JiraHandle>) -> Self {
+        ActiveSprint {
+            db,
+            selection: Selection::default(),
+        }
+    }
+
+    // story ids of each status, in the order the groups are drawn
+    fn groups(db_state: &DBState) -> Vec<(Status, Vec<u32>)> {
+        let Some(sprint) = db_state
+            .active_sprint()
+            .map(|id| &db_state.sprints[&id])
+        else {
+            return vec![];
+        };
+        Status::ALL
+            .iter()
+            .map(|status| {
+                let mut ids: Vec<u32> = sprint
+                    .stories
+                    .iter()
+                    .copied()
+                    .filter(|id| {
+                        db_state
+                            .stories
+                            .get(id)
+                            .is_some_and(|story| story.status == *status)
+                    })
+                    .collect();
+                ids.sort_unstable();
+                (*status, ids)
+            })
+            .collect()
+    }
+}
+
+impl Page for ActiveSprint {
+    fn draw_page(&self, frame: &mut Frame) -> Result<()> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+
+        frame.title("ACTIVE SPRINT");
+        let Some(sprint_id) = db_state.active_sprint() else {
+            frame.line("no sprint is active, start one from the sprint list");
+            frame.line("");
+            frame.line("[p] previous");
+            return Ok(());
+        };
+        sprint_header(frame, sprint_id, &db_state.sprints[&sprint_id]);
+
+        let groups = ActiveSprint::groups(&db_state);
+        let selected = self
+            .selection
+            .selected(&self.selectable_ids()?);
+        let table = story_table(frame.width());
+        for (status, ids) in groups.iter() {
+            frame.line("");
+            frame.title(&format!("{} ({})", status, ids.len()));
+            for id in ids.iter() {
+                story_row(
+                    frame,
+                    &table,
+                    *id,
+                    &db_state.stories[id],
+                    &db_state,
+                    selected == Some(*id),
+                );
+            }
+        }
+
+        frame.line("");
+        frame.line("");
+
+        frame.line("[p] previous | [u] update selected story | [x] complete sprint | [enter] or [:id:] navigate to story");
+
+        Ok(())
+    }
+
+    fn handle_input(&self, input: &str) -> Result<Option<Action>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        let Some(sprint_id) = db_state.active_sprint() else {
+            return Ok((input == "p").then_some(Action::NavigateToPreviousPage));
+        };
+        let ids = self.selectable_ids()?;
+
+        match input {
+            "p" => Ok(Some(Action::NavigateToPreviousPage)),
+            "x" => Ok(Some(Action::CompleteSprint { sprint_id })),
+            "u" => Ok(self
+                .selection
+                .selected(&ids)
+                .map(|story_id| Action::UpdateStoryStatus { story_id })),
+            input => Ok(input
+                .parse::<u32>()
+                .ok()
+                .filter(|story_id| ids.contains(story_id))
+                .map(|story_id| navigate_to_story(&db_state, story_id))),
+        }
+    }
+
+    fn selectable_ids(&self) -> Result<Vec<u32>> {
+        let db_state = self
+            .db
+            .read_full_record()?;
+        Ok(ActiveSprint::groups(&db_state)
+            .into_iter()
+            .flat_map(|(_, ids)| ids)
+            .collect())
+    }
+
+    fn selection(&self) -> Option<&Selection> {
+        Some(&self.selection)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::test_utils::MockDB;
+    use crate::models::{Epic, Story};
+
+    fn setup() -> (Rc<JiraHandle>, u32, u32, u32) {
+        let db = Rc::new(JiraHandle {
+            database: Box::new(MockDB::new()),
+        });
+        let epic_id = db
+            .create_epic(Epic::new("epic".to_owned(), "".to_owned()))
+            .unwrap();
+        let planned_id = db
+            .create_story(Story::new("planned".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let candidate_id = db
+            .create_story(Story::new("candidate".to_owned(), "".to_owned()), epic_id)
+            .unwrap();
+        let sprint_id = db
+            .create_sprint(Sprint::new(
+                "sprint".to_owned(),
+                "ship it".to_owned(),
+                chrono::NaiveDate::from_ymd_opt(2024, 1, 1),
+                None,
+            ))
+            .unwrap();
+        db.add_story_to_sprint(sprint_id, planned_id)
+            .unwrap();
+        (db, sprint_id, planned_id, candidate_id)
+    }
+
+    #[test]
+    fn sprints_page_should_list_sprints_and_open_planning() {
+        let (db, sprint_id, _, _) = setup();
+        let page = SprintsPage::new(db);
+
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+        assert!(frame
+            .lines()
+            .iter()
+            .any(|line| line.contains("2024-01-01 - ?") && line.contains("PLANNED")));
+
+        assert_eq!(
+            page.handle_input(&sprint_id.to_string())
+                .unwrap(),
+            Some(Action::NavigateToSprintPlanning { sprint_id })
+        );
+        assert_eq!(
+            page.handle_input("c")
+                .unwrap(),
+            Some(Action::CreateSprint)
+        );
+        assert_eq!(
+            page.handle_input("a")
+                .unwrap(),
+            Some(Action::NavigateToActiveSprint)
+        );
+    }
+
+    #[test]
+    fn sprint_planning_should_move_stories_between_lists() {
+        let (db, sprint_id, planned_id, candidate_id) = setup();
+        let page = SprintPlanning::new(sprint_id, db);
+
+        assert_eq!(
+            page.selectable_ids()
+                .unwrap(),
+            vec![planned_id, candidate_id]
+        );
+        assert_eq!(
+            page.handle_input(&planned_id.to_string())
+                .unwrap(),
+            Some(Action::RemoveStoryFromSprint {
+                sprint_id,
+                story_id: planned_id
+            })
+        );
+        assert_eq!(
+            page.handle_input(&candidate_id.to_string())
+                .unwrap(),
+            Some(Action::AddStoryToSprint {
+                sprint_id,
+                story_id: candidate_id
+            })
+        );
+        assert_eq!(
+            page.handle_input("s")
+                .unwrap(),
+            Some(Action::StartSprint { sprint_id })
+        );
+
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+        assert!(frame
+            .lines()
+            .iter()
+            .any(|line| line == "goal: ship it"));
+    }
+
+    #[test]
+    fn active_sprint_should_group_stories_by_status() {
+        let (db, sprint_id, planned_id, candidate_id) = setup();
+        db.add_story_to_sprint(sprint_id, candidate_id)
+            .unwrap();
+        db.update_story_status(candidate_id, Status::InProgress)
+            .unwrap();
+        let page = ActiveSprint::new(Rc::clone(&db));
+
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+        assert!(frame
+            .lines()
+            .iter()
+            .any(|line| line.starts_with("no sprint is active")));
+
+        db.start_sprint(sprint_id)
+            .unwrap();
+        let mut frame = Frame::default();
+        page.draw_page(&mut frame)
+            .unwrap();
+        let lines = frame.lines();
+        let open = lines
+            .iter()
+            .position(|line| line.contains(" OPEN (1) "))
+            .unwrap();
+        assert!(lines[open + 1].contains("planned"));
+        let in_progress = lines
+            .iter()
+            .position(|line| line.contains(" IN PROGRESS (1) "))
+            .unwrap();
+        assert!(lines[in_progress + 1].contains("candidate"));
+
+        assert_eq!(
+            page.handle_input("x")
+                .unwrap(),
+            Some(Action::CompleteSprint { sprint_id })
+        );
+        assert_eq!(
+            page.handle_input("u")
+                .unwrap(),
+            Some(Action::UpdateStoryStatus {
+                story_id: planned_id
+            })
+        );
+        assert_eq!(
+            page.handle_input(&candidate_id.to_string())
+                .unwrap(),
+            Some(Action::NavigateToStoryDetail {
+                epic_id: 1,
+                story_id: candidate_id
+            })
+        );
+    }
+}
@@ -1,111 +1,632 @@
-use crate::{models::{Epic, Story, Status}, io_utils::get_user_input};
-
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use chrono::NaiveDate;
+
+use crate::{
+    config::Config,
+    dates::{parse_date, today},
+    fields::{FieldDef, FieldFilter},
+    io_utils::{edit_text, editor_command, prompt_key, prompt_line},
+    models::{Epic, IssueType, LinkKind, Priority, Severity, Sprint, Status, Story, SubTask},
+    templates::{templates_of, Template, TemplateKind},
+};
+
+use super::editor::{parse_form, render_form, with_error, FormKind, ItemForm};
+
+// asked with the unfinished stories of a completed sprint
+type RollOverPrompt = dyn Fn(&[u32]) -> bool;
+// asked with the open blockers of a story about to be started
+type StartBlockedPrompt = dyn Fn(&[u32]) -> bool;
+// a new epic and the stories its template creates with it
+type CreateEpicPrompt = dyn Fn() -> Option<(Epic, Vec<Story>)>;
+// asked with the current custom field values of an epic or story; returns
+// the field to change and its new value, None clearing it
+type UpdateFieldPrompt = dyn Fn(&BTreeMap<String, String>) -> Option<(String, Option<String>)>;
+
+// each prompt returns None when the user cancels the modal with escape
 pub struct Prompts {
-    pub create_epic: Box<dyn Fn() -> Epic>,
-    pub create_story: Box<dyn Fn() -> Story>,
+    pub create_epic: Box<CreateEpicPrompt>,
+    pub create_story: Box<dyn Fn() -> Option<Story>>,
     pub delete_epic: Box<dyn Fn() -> bool>,
     pub delete_story: Box<dyn Fn() -> bool>,
-    pub update_status: Box<dyn Fn() -> Option<Status>>
+    pub create_subtask: Box<dyn Fn() -> Option<SubTask>>,
+    pub delete_subtask: Box<dyn Fn() -> bool>,
+    pub attach_story: Box<dyn Fn() -> Option<u32>>,
+    pub create_sprint: Box<dyn Fn() -> Option<Sprint>>,
+    pub roll_over: Box<RollOverPrompt>,
+    pub update_status: Box<dyn Fn() -> Option<Status>>,
+    pub update_priority: Box<dyn Fn() -> Option<Priority>>,
+    // Some(None) clears the estimate
+    pub update_points: Box<dyn Fn() -> Option<Option<u32>>>,
+    // Some(None) clears the due date
+    pub update_due: Box<dyn Fn() -> Option<Option<NaiveDate>>>,
+    // asked when a timer is stopped; None keeps it running
+    pub worklog_note: Box<dyn Fn() -> Option<String>>,
+    pub link_story: Box<dyn Fn() -> Option<(LinkKind, u32)>>,
+    pub unlink_story: Box<dyn Fn() -> Option<u32>>,
+    pub start_blocked: Box<StartBlockedPrompt>,
+    pub update_field: Box<UpdateFieldPrompt>,
+    // Some(None) clears the filter
+    pub field_filter: Box<dyn Fn() -> Option<Option<FieldFilter>>>,
+    // key and name of a new project
+    pub create_project: Box<dyn Fn() -> Option<(String, String)>>,
 }
 
 impl Prompts {
     pub fn new() -> Self {
-        Self { 
-            create_epic: Box::new(create_epic_prompt),
-            create_story: Box::new(create_story_prompt),
+        Self::with_config(&Config::default())
+    }
+
+    /// Prompts that also offer the templates and ask for the custom fields
+    /// of `config`.
+    pub fn with_config(config: &Config) -> Self {
+        let defs: Rc<[FieldDef]> = config
+            .fields
+            .clone()
+            .into();
+        let templates: Rc<[Template]> = config
+            .templates
+            .clone()
+            .into();
+        let (epic_defs, epic_templates) = (Rc::clone(&defs), Rc::clone(&templates));
+        let (story_defs, story_templates) = (Rc::clone(&defs), templates);
+        let field_defs = Rc::clone(&defs);
+        Self {
+            create_epic: Box::new(move || create_epic_prompt(&epic_defs, &epic_templates)),
+            create_story: Box::new(move || create_story_prompt(&story_defs, &story_templates)),
             delete_epic: Box::new(delete_epic_prompt),
             delete_story: Box::new(delete_story_prompt),
-            update_status: Box::new(update_status_prompt)
+            create_subtask: Box::new(create_subtask_prompt),
+            delete_subtask: Box::new(delete_subtask_prompt),
+            attach_story: Box::new(attach_story_prompt),
+            create_sprint: Box::new(create_sprint_prompt),
+            roll_over: Box::new(roll_over_prompt),
+            update_status: Box::new(update_status_prompt),
+            update_priority: Box::new(update_priority_prompt),
+            update_points: Box::new(update_points_prompt),
+            update_due: Box::new(update_due_prompt),
+            worklog_note: Box::new(worklog_note_prompt),
+            link_story: Box::new(link_story_prompt),
+            unlink_story: Box::new(unlink_story_prompt),
+            start_blocked: Box::new(start_blocked_prompt),
+            update_field: Box::new(move |fields| update_field_prompt(&field_defs, fields)),
+            field_filter: Box::new(move || field_filter_prompt(&defs)),
+            create_project: Box::new(create_project_prompt),
         }
     }
 }
 
-fn create_epic_prompt() -> Epic {
-    println!("----------------------------");
-
-    println!("Epic Name: ");
+impl Default for Prompts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    let epic_name = get_user_input();
+fn create_epic_prompt(defs: &[FieldDef], templates: &[Template]) -> Option<(Epic, Vec<Story>)> {
+    let template = template_prompt("Create epic", &templates_of(templates, TemplateKind::Epic))?;
+    let epic = item_prompt("Create epic", FormKind::Epic, defs, template)?.into_epic();
+    let stories = template.map_or(vec![], |template| {
+        template
+            .stories
+            .iter()
+            .map(|story| story.story(defs, today()))
+            .collect()
+    });
+    Some((epic, stories))
+}
 
-    println!("Epic Description: ");
+fn create_story_prompt(defs: &[FieldDef], templates: &[Template]) -> Option<Story> {
+    let template = template_prompt(
+        "Create story",
+        &templates_of(templates, TemplateKind::Story),
+    )?;
+    item_prompt("Create story", FormKind::Story, defs, template).map(ItemForm::into_story)
+}
 
-    let epic_desc = get_user_input();
+// Some(None) for a blank item, None when cancelled; only asked when the
+// config has templates for the item
+fn template_prompt<'a>(title: &str, templates: &[&'a Template]) -> Option<Option<&'a Template>> {
+    if templates.is_empty() {
+        return Some(None);
+    }
 
-    let epic = Epic::new(epic_name.trim().to_owned(), epic_desc.trim().to_owned());
+    // the first nine are picked with a single key, longer lists by name
+    if templates.len() < 10 {
+        let choices: Vec<String> = templates
+            .iter()
+            .enumerate()
+            .map(|(index, template)| format!("{} - {}", index + 1, template.name))
+            .collect();
+        let key = prompt_key(
+            title,
+            &format!("Template (0 - blank, {})", choices.join(", ")),
+        )
+        .ok()??;
+        return match key {
+            '0' => Some(None),
+            key => key
+                .to_digit(10)
+                .and_then(|digit| templates.get(digit as usize - 1))
+                .map(|template| Some(*template)),
+        };
+    }
 
-    epic
+    let names: Vec<&str> = templates
+        .iter()
+        .map(|template| {
+            template
+                .name
+                .as_str()
+        })
+        .collect();
+    let name = prompt_line(
+        title,
+        &format!("Template ({}, leave empty for blank):", names.join(", ")),
+    )
+    .ok()??;
+    if name
+        .trim()
+        .is_empty()
+    {
+        return Some(None);
+    }
+    templates
+        .iter()
+        .find(|template| {
+            template
+                .name
+                .eq_ignore_ascii_case(name.trim())
+        })
+        .map(|template| Some(*template))
 }
 
-fn create_story_prompt() -> Story {
-    println!("----------------------------");
+// uses $EDITOR when one is set so descriptions can span several lines,
+// otherwise asks for each field in a modal
+fn item_prompt(
+    title: &str,
+    kind: FormKind,
+    defs: &[FieldDef],
+    template: Option<&Template>,
+) -> Option<ItemForm> {
+    let form = template.map_or(ItemForm::new(String::new(), String::new()), |template| {
+        ItemForm::from_template(template, defs)
+    });
+    match editor_command() {
+        Some(editor) => editor_prompt(&editor, title, kind, defs, &form),
+        None => modal_prompt(title, kind, defs, form, template.is_some()),
+    }
+}
 
-    println!("Story Name: ");
+fn editor_prompt(
+    editor: &str,
+    title: &str,
+    kind: FormKind,
+    defs: &[FieldDef],
+    form: &ItemForm,
+) -> Option<ItemForm> {
+    let template = render_form(title, kind, defs, form);
+    let mut text = template.clone();
+    loop {
+        text = edit_text(editor, &text).ok()??;
+        // a template saved without changes cancels like an empty form
+        if text == template {
+            return None;
+        }
+        match parse_form(&text, kind, defs) {
+            Ok(form) => return form,
+            Err(error) => text = with_error(&text, &error),
+        }
+    }
+}
 
-    let story_name = get_user_input();
+// values already in `form` come from a template and are kept when the
+// answer is left empty
+fn modal_prompt(
+    title: &str,
+    kind: FormKind,
+    defs: &[FieldDef],
+    mut form: ItemForm,
+    from_template: bool,
+) -> Option<ItemForm> {
+    let label = match kind {
+        FormKind::Epic => "Epic",
+        FormKind::Story => {
+            if !from_template {
+                form.issue_type = issue_type_prompt(title)?;
+            }
+            "Story"
+        }
+    };
+
+    let name = prompt_line(title, &format!("{} Name:", label)).ok()??;
+    form.name = name
+        .trim()
+        .to_owned();
+
+    let prompt = if form
+        .description
+        .is_empty()
+    {
+        format!("{} Description:", label)
+    } else {
+        format!(
+            "{} Description (leave empty to keep the template's):",
+            label
+        )
+    };
+    let description = prompt_line(title, &prompt).ok()??;
+    if !description
+        .trim()
+        .is_empty()
+    {
+        form.description = description
+            .trim()
+            .to_owned();
+    }
 
-    println!("Story Description: ");
+    if form.issue_type == IssueType::Bug {
+        form.steps_to_reproduce = prompt_line(title, "Steps to reproduce:")
+            .ok()??
+            .trim()
+            .to_owned();
+        form.severity = severity_prompt(title)?;
+    }
 
-    let story_desc = get_user_input();
+    for def in defs {
+        let default = form
+            .fields
+            .get(&def.name)
+            .map_or("none".to_owned(), |value| value.clone());
+        let label = format!(
+            "{} ({}, leave empty for {}):",
+            def.name,
+            def.hint(),
+            default
+        );
+        if let Some(value) = field_value_prompt(title, def, &label)? {
+            form.fields
+                .insert(
+                    def.name
+                        .clone(),
+                    value,
+                );
+        }
+    }
 
-    let story = Story::new(story_name.trim().to_owned(), story_desc.trim().to_owned());
+    Some(form)
+}
 
-    story
+// Some(None) when the value was left empty, None when it is cancelled;
+// asks again with the reason when the value does not fit the field
+fn field_value_prompt(title: &str, def: &FieldDef, label: &str) -> Option<Option<String>> {
+    let mut prompt = label.to_owned();
+    loop {
+        let value = prompt_line(title, &prompt).ok()??;
+        match def.parse(&value, today()) {
+            Ok(value) => return Some(value),
+            Err(err) => prompt = format!("{} {}", err, label),
+        }
+    }
 }
 
-fn delete_epic_prompt() -> bool {
-    println!("----------------------------");
+fn issue_type_prompt(title: &str) -> Option<IssueType> {
+    let issue_type = prompt_key(title, "Type (1 - STORY, 2 - BUG, 3 - TASK, 4 - SPIKE)").ok()??;
 
-    println!("Are you sure you want to delete this epic? All stories in this epic will also be deleted [Y/n]: ");
+    match issue_type {
+        '1' => Some(IssueType::Story),
+        '2' => Some(IssueType::Bug),
+        '3' => Some(IssueType::Task),
+        '4' => Some(IssueType::Spike),
+        _ => None,
+    }
+}
 
-    let input = get_user_input();
+fn severity_prompt(title: &str) -> Option<Severity> {
+    let severity = prompt_key(title, "Severity (1 - MINOR, 2 - MAJOR, 3 - CRITICAL)").ok()??;
 
-    if input.trim().eq("Y") {
-        return true;
+    match severity {
+        '1' => Some(Severity::Minor),
+        '2' => Some(Severity::Major),
+        '3' => Some(Severity::Critical),
+        _ => None,
     }
+}
+
+fn delete_epic_prompt() -> bool {
+    let input = prompt_key(
+        "Delete epic",
+        "Are you sure you want to delete this epic? All stories in this epic will also be deleted [Y/n]",
+    );
 
-    false
+    matches!(input, Ok(Some('Y')))
 }
 
 fn delete_story_prompt() -> bool {
-    println!("----------------------------");
+    let input = prompt_key(
+        "Delete story",
+        "Are you sure you want to delete this story? [Y/n]",
+    );
+
+    matches!(input, Ok(Some('Y')))
+}
+
+fn create_subtask_prompt() -> Option<SubTask> {
+    let name = prompt_line("Create sub-task", "Sub-task Name:").ok()??;
+
+    let assignee = prompt_line("Create sub-task", "Assignee (leave empty for none):").ok()??;
+    let assignee = assignee.trim();
 
-    println!("Are you sure you want to delete this story? [Y/n]: ");
+    Some(SubTask::new(
+        name.trim()
+            .to_owned(),
+        (!assignee.is_empty()).then(|| assignee.to_owned()),
+    ))
+}
+
+fn delete_subtask_prompt() -> bool {
+    let input = prompt_key(
+        "Delete sub-task",
+        "Are you sure you want to delete this sub-task? [Y/n]",
+    );
 
-    let input = get_user_input();
+    matches!(input, Ok(Some('Y')))
+}
 
-    if input.trim().eq("Y") {
-        return true;
+// id of the epic a backlog story should move to
+fn attach_story_prompt() -> Option<u32> {
+    let epic_id = prompt_line("Attach story", "Epic id:").ok()??;
+
+    epic_id
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn create_sprint_prompt() -> Option<Sprint> {
+    let name = prompt_line("Create sprint", "Sprint Name:").ok()??;
+
+    let goal = prompt_line("Create sprint", "Sprint Goal:").ok()??;
+
+    let start = date_prompt(
+        "Create sprint",
+        "Start date (e.g. 2026-11-01, +3d or friday, leave empty for none):",
+    )?;
+
+    let end = date_prompt(
+        "Create sprint",
+        "End date (e.g. 2026-11-01, +3d or friday, leave empty for none):",
+    )?;
+
+    Some(Sprint::new(
+        name.trim()
+            .to_owned(),
+        goal.trim()
+            .to_owned(),
+        start,
+        end,
+    ))
+}
+
+// the key is uppercased; the database checks that it is a valid key
+fn create_project_prompt() -> Option<(String, String)> {
+    let key = prompt_line("Create project", "Project Key (e.g. API):").ok()??;
+
+    let name = prompt_line("Create project", "Project Name:").ok()??;
+
+    Some((
+        key.trim()
+            .to_uppercase(),
+        name.trim()
+            .to_owned(),
+    ))
+}
+
+// Some(None) when the date was left empty, None when it is cancelled; asks
+// again with the reason when the input is not a date
+fn date_prompt(title: &str, label: &str) -> Option<Option<NaiveDate>> {
+    let mut prompt = label.to_owned();
+    loop {
+        let date = prompt_line(title, &prompt).ok()??;
+        if date
+            .trim()
+            .is_empty()
+        {
+            return Some(None);
+        }
+
+        match parse_date(&date, today()) {
+            Ok(date) => return Some(Some(date)),
+            Err(err) => prompt = format!("{} {}", err, label),
+        }
     }
+}
 
-    false
+fn roll_over_prompt(unfinished: &[u32]) -> bool {
+    let ids: Vec<String> = unfinished
+        .iter()
+        .map(u32::to_string)
+        .collect();
+    let input = prompt_key(
+        "Complete sprint",
+        &format!(
+            "{} unfinished stories ({}). Roll them into the next sprint? [Y/n]",
+            unfinished.len(),
+            ids.join(", ")
+        ),
+    );
+
+    matches!(input, Ok(Some('Y')))
 }
 
 fn update_status_prompt() -> Option<Status> {
-    println!("----------------------------");
+    let status = prompt_key(
+        "Update status",
+        "New Status (1 - OPEN, 2 - IN-PROGRESS, 3 - RESOLVED, 4 - CLOSED)",
+    )
+    .ok()??;
+
+    match status {
+        '1' => Some(Status::Open),
+        '2' => Some(Status::InProgress),
+        '3' => Some(Status::Resolved),
+        '4' => Some(Status::Closed),
+        _ => None,
+    }
+}
 
-    println!("New Status (1 - OPEN, 2 - IN-PROGRESS, 3 - RESOLVED, 4 - CLOSED): ");
+fn update_priority_prompt() -> Option<Priority> {
+    let priority = prompt_key(
+        "Update priority",
+        "New Priority (1 - LOW, 2 - MEDIUM, 3 - HIGH)",
+    )
+    .ok()??;
+
+    match priority {
+        '1' => Some(Priority::Low),
+        '2' => Some(Priority::Medium),
+        '3' => Some(Priority::High),
+        _ => None,
+    }
+}
 
-    let status = get_user_input();
+fn update_points_prompt() -> Option<Option<u32>> {
+    let points = prompt_line("Estimate story", "Story points (leave empty to clear):").ok()??;
+    let points = points.trim();
+    if points.is_empty() {
+        return Some(None);
+    }
 
-    let status = status.trim().parse::<u8>();
+    points
+        .parse()
+        .ok()
+        .map(Some)
+}
 
-    if let Ok(status) = status {
-        match status {
-            1 => {
-                return Some(Status::Open);
-            }
-            2 => {
-                return Some(Status::InProgress);
-            }
-            3 => {
-                return Some(Status::Resolved);
-            }
-            4 => {
-                return Some(Status::Closed);
-            }
-            _ => return None
-        }
+fn update_due_prompt() -> Option<Option<NaiveDate>> {
+    date_prompt(
+        "Update due date",
+        "Due date (e.g. 2026-11-01, +3d or friday, leave empty to clear):",
+    )
+}
+
+fn worklog_note_prompt() -> Option<String> {
+    let note = prompt_line("Stop timer", "What did you work on? (optional):").ok()??;
+
+    Some(
+        note.trim()
+            .to_owned(),
+    )
+}
+
+fn link_story_prompt() -> Option<(LinkKind, u32)> {
+    let kind = prompt_key(
+        "Link story",
+        "Link type (1 - BLOCKS, 2 - IS BLOCKED BY, 3 - RELATES TO, 4 - DUPLICATES)",
+    )
+    .ok()??;
+    let kind = match kind {
+        '1' => LinkKind::Blocks,
+        '2' => LinkKind::IsBlockedBy,
+        '3' => LinkKind::RelatesTo,
+        '4' => LinkKind::Duplicates,
+        _ => return None,
+    };
+
+    let story_id = prompt_line("Link story", &format!("{} story id:", kind)).ok()??;
+
+    story_id
+        .trim()
+        .parse()
+        .ok()
+        .map(|story_id| (kind, story_id))
+}
+
+fn unlink_story_prompt() -> Option<u32> {
+    let story_id = prompt_line("Unlink story", "Remove the links to story id:").ok()??;
+
+    story_id
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn start_blocked_prompt(blockers: &[u32]) -> bool {
+    let ids: Vec<String> = blockers
+        .iter()
+        .map(u32::to_string)
+        .collect();
+    let input = prompt_key(
+        "Update status",
+        &format!(
+            "This story is blocked by {} which are not done yet. Start it anyway? [y/N]",
+            ids.join(", ")
+        ),
+    );
+
+    matches!(input, Ok(Some('y' | 'Y')))
+}
+
+fn update_field_prompt(
+    defs: &[FieldDef],
+    fields: &BTreeMap<String, String>,
+) -> Option<(String, Option<String>)> {
+    if defs.is_empty() {
+        let _ = prompt_key(
+            "Update field",
+            "No custom fields are set up, add them to the config first [any key]",
+        );
+        return None;
     }
+    let names: Vec<&str> = defs
+        .iter()
+        .map(|def| {
+            def.name
+                .as_str()
+        })
+        .collect();
+
+    let name = prompt_line("Update field", &format!("Field ({}):", names.join(", "))).ok()??;
+    let def = defs
+        .iter()
+        .find(|def| {
+            def.name
+                .eq_ignore_ascii_case(name.trim())
+        })?;
+
+    let current = fields
+        .get(&def.name)
+        .map_or("none".to_owned(), |value| value.clone());
+    let label = format!(
+        "{} ({}, currently {}, leave empty to clear):",
+        def.name,
+        def.hint(),
+        current
+    );
+    let value = field_value_prompt("Update field", def, &label)?;
+
+    Some((
+        def.name
+            .clone(),
+        value,
+    ))
+}
 
-    None
-}
\ No newline at end of file
+fn field_filter_prompt(defs: &[FieldDef]) -> Option<Option<FieldFilter>> {
+    let label = "Show stories where (e.g. customer=acme, leave empty to show all):";
+    let mut prompt = label.to_owned();
+    loop {
+        let filter = prompt_line("Filter stories", &prompt).ok()??;
+        if filter
+            .trim()
+            .is_empty()
+        {
+            return Some(None);
+        }
+
+        match FieldFilter::parse(&filter, defs, today()) {
+            Ok(filter) => return Some(Some(filter)),
+            Err(err) => prompt = format!("{} {}", err, label),
+        }
+    }
+}